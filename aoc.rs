@@ -0,0 +1,785 @@
+#[cfg(target_os = "linux")]
+use shared::watch;
+use shared::{
+    InputSource, Solution, Status, batch, bench,
+    budget::{self, Budget},
+    client::{Client, Verdict},
+    day_1, day_2, day_3, day_4, day_5, day_6, day_7, day_8, day_9, day_10, day_11, day_12,
+    error::{self, EXIT_BUDGET_EXCEEDED, EXIT_FAILURE, Error},
+    generate::Rng,
+    num,
+    output::{self, Format},
+    pool,
+    verify::{self, Outcome},
+    visualize::{self, Playback},
+};
+#[cfg(target_os = "linux")]
+use std::convert::Infallible;
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+const USAGE: &str = "usage:
+  aoc run <days> [--part <1|2>] [--input <path|->] [--format <text|json>] [--jobs <n>] [--stats]
+          [--watch] [<solving>]
+  aoc bench <days> [--part <1|2>] [--input <path|->] [--runs <n>] [--output <file>] [<solving>]
+  aoc batch <day> <dir|glob> [--part <1|2>] [--format <text|json>] [--jobs <n>] [<solving>]
+  aoc visualize <day> [--part <1|2>] [--input <path|->] [--fps <n>] [--frames <dir>]
+          [--max-frames <n>] [--no-color] [<solving>]
+  aoc verify <days> [--part <1|2>] [--answers <file>] [<solving>]
+  aoc generate <days> [--size <n>] [--seed <n>] [--output <dir>]
+  aoc fetch <days> [--input <path>] [--force] [--base-url <url>]
+  aoc submit <day> --part <1|2> [--answer <value>] [--input <path>] [--base-url <url>]
+          [<solving>]
+
+<solving> is any of [--timeout <seconds>] [--max-steps <n|none>]
+[--arithmetic <checked|wide>] [--wide].
+
+<days> is `all`, a day, or a comma-separated list of days and ranges such as
+`1,4-6,12`. Day 12 has no part 2, so `--part 2` skips it when several days
+are selected and is an error when it is the only one.
+
+Inputs are read from `--input` (single day only, `-` for stdin), otherwise
+from `$AOC_INPUT_DIR/<day>.txt`, otherwise from `./<day>.txt`.
+
+`run` solves up to `--jobs` days at once (default: one per CPU) and prints
+their answers in day order, followed by a summary table when more than one
+day was selected. `run --format json` prints a JSON array with one object per
+answer (day, part, answer, input, elapsed_ns, status) or per failed day
+(status `error`).
+`run --stats` also prints the counters, gauges and timers each part recorded
+(a `stats` object per answer in JSON).
+
+`run --watch` (single day, text only, Linux only) solves the input again
+whenever it changes, showing how each answer and time moved. Under `cargo run`
+(or with `$CARGO_MANIFEST_DIR` set), editing the sources rebuilds and restarts
+the watcher.
+
+`batch` solves one day on every input in a directory or matching a glob such
+as `inputs/7-*.txt` (wildcards in the file name only), up to `--jobs` at once,
+and prints a table of file, status, time and answers. An input that fails to
+read, parse or solve (even by panicking) only fails its own row.
+
+Answers are computed in 64-bit integers and overflow is an error (exit code
+7); `--arithmetic wide` (or `--wide`) computes them in 128 bits instead.
+
+`--timeout` and `--max-steps` limit the time and search steps of each part
+(default: no time limit, 10000000 steps). A part that runs out prints
+`unknown (out of budget)` (a null answer in JSON) and fails the run.
+
+`bench` times parsing and each part over `--runs` runs (default 10) and
+prints min/median/max per phase; `--output` also writes them as TSV.
+
+`visualize` (days 4, 7 and 12) solves a day while recording each step of its
+grid, then plays the steps back as colored frames at `--fps` frames per second
+(default 10; 0 waits for Enter before each frame), underlining the cells that
+changed. `--frames` writes the frames to numbered text files in a directory
+instead, and `--no-color` prints them one after another without ANSI escapes.
+At most `--max-frames` (default 10000) frames are kept, always including the
+last.
+
+`verify` checks answers against `--answers` (default `./answers.txt`), a file
+of `day part input expected` lines with inputs relative to the file.
+
+`generate` writes random valid input for a day, scaled by `--size` (default
+10) and reproducible from `--seed` (default 0). A single day is printed unless
+`--output` names a directory, which receives one `<day>.txt` per day.
+
+`fetch` downloads each day's input to the path `run` reads it from, skipping
+inputs that already exist unless `--force` is given. `submit` posts
+`--answer` (default: the part's answer on the day's input) and prints the
+verdict. Both log in with the session cookie in `$AOC_SESSION` and talk to
+`--base-url`, else `$AOC_BASE_URL`, else https://adventofcode.com.
+
+Exit codes: 0 success, 1 wrong answers (`verify`, `submit`), 2 usage, 3 I/O
+error, 4 parse error, 5 no solution, 6 budget exceeded, 7 overflow, 101 panic
+(`batch`). When several days fail, the first failure's code is used.";
+
+const DEFAULT_RUNS: usize = 10;
+const DEFAULT_ANSWERS: &str = "./answers.txt";
+const DEFAULT_SIZE: usize = 10;
+
+/// Entry points for one day, instantiated for its `Solution`.
+struct Day {
+    parts: u8,
+    run: fn(&InputSource, Option<u8>) -> error::Result<shared::Answers>,
+    #[cfg(target_os = "linux")]
+    watch: fn(&InputSource, Option<u8>, bool) -> error::Result<Infallible>,
+    bench: fn(&str, Option<u8>, usize) -> error::Result<Vec<bench::Timing>>,
+    batch: fn(&[PathBuf], Option<u8>, usize) -> Vec<batch::Entry>,
+    visualize: fn(&InputSource, Option<u8>, usize) -> error::Result<visualize::Recorded>,
+    generate: fn(&mut Rng, usize) -> String,
+}
+
+const fn day<S: Solution>() -> Day {
+    Day {
+        parts: S::PARTS,
+        run: shared::run::<S>,
+        #[cfg(target_os = "linux")]
+        watch: watch::watch::<S>,
+        bench: bench::bench::<S>,
+        batch: batch::run_batch::<S>,
+        visualize: visualize::run_recorded::<S>,
+        generate: S::generate,
+    }
+}
+
+// Indexed by day minus one.
+const DAYS: [Day; 12] = [
+    day::<day_1::Day1>(),
+    day::<day_2::Day2>(),
+    day::<day_3::Day3>(),
+    day::<day_4::Day4>(),
+    day::<day_5::Day5>(),
+    day::<day_6::Day6>(),
+    day::<day_7::Day7>(),
+    day::<day_8::Day8>(),
+    day::<day_9::Day9>(),
+    day::<day_10::Day10>(),
+    day::<day_11::Day11>(),
+    day::<day_12::Day12>(),
+];
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    let options = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            eprintln!("{}", USAGE);
+            return ExitCode::from(&error);
+        }
+    };
+
+    budget::configure(options.budget.clone());
+    num::configure(options.mode);
+    let result = match options.command {
+        #[cfg(target_os = "linux")]
+        Command::Run if options.watch => run_watch(&options),
+        Command::Run => run(&options),
+        Command::Bench => run_bench(&options),
+        Command::Batch => run_batch(&options),
+        Command::Visualize => run_visualize(&options),
+        Command::Verify => run_verify(&options),
+        Command::Generate => run_generate(&options),
+        Command::Fetch => run_fetch(&options),
+        Command::Submit => run_submit(&options),
+    };
+    match result {
+        Ok(0) => ExitCode::SUCCESS,
+        Ok(code) => ExitCode::from(code),
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::from(&error)
+        }
+    }
+}
+
+/// Keeps the exit code of the first failing day.
+fn record_failure(code: &mut u8, error: &Error) {
+    if *code == 0 {
+        *code = error.exit_code();
+    }
+}
+
+/// Solves the selected day whenever its input changes, until interrupted.
+#[cfg(target_os = "linux")]
+fn run_watch(options: &Options) -> error::Result<u8> {
+    let day = options.days[0];
+    let source = InputSource::resolve(day, options.input.as_deref());
+    let Err(error) = (DAYS[day as usize - 1].watch)(&source, options.part, options.stats);
+    Err(error)
+}
+
+/// Prints every answer, returning the exit code of the first failing day (or
+/// zero).
+fn run(options: &Options) -> error::Result<u8> {
+    // Days run concurrently but are reported in order, and a failing day does
+    // not stop the others so that `all` reports every result.
+    let start = Instant::now();
+    let results = pool::map(&options.days, options.jobs, |day| {
+        let source = InputSource::resolve(*day, options.input.as_deref());
+        let day_start = Instant::now();
+        let result = (DAYS[*day as usize - 1].run)(&source, options.part);
+        (source, result, day_start.elapsed())
+    });
+    let wall = start.elapsed();
+
+    let mut code = 0;
+    let mut records = Vec::new();
+    for (day, (source, result, _)) in options.days.iter().zip(&results) {
+        if options.format == Format::Json {
+            records.extend(output::json_records(*day, source, result, options.stats));
+        }
+        match result {
+            Ok(answers) => {
+                // Parts that ran out of budget are reported like any other
+                // answer but still fail the run.
+                if code == 0 && answers.iter().any(|answer| answer.value.is_none()) {
+                    code = EXIT_BUDGET_EXCEEDED;
+                }
+                if options.format == Format::Text {
+                    print_answers(*day, answers, options.stats);
+                }
+            }
+            Err(error) => {
+                eprintln!("day {} error: {}", day, error);
+                record_failure(&mut code, error);
+            }
+        }
+    }
+
+    if options.format == Format::Json {
+        println!("{}", output::json_array(&records));
+    } else if options.days.len() > 1 {
+        print_summary(&options.days, &results, wall);
+    }
+
+    Ok(code)
+}
+
+fn print_answers(day: u8, answers: &shared::Answers, stats: bool) {
+    for answer in answers {
+        let text = match (&answer.value, answer.status) {
+            (Some(value), Status::Solved) => value.clone(),
+            (Some(value), Status::Unknown) => format!("{} (unknown)", value),
+            (None, _) => "unknown (out of budget)".to_string(),
+        };
+        println!("day {} part {}: {}", day, answer.part, text);
+    }
+    if stats {
+        for answer in answers {
+            let heading = format!("day {} part {}", day, answer.part);
+            print!("{}", output::stats_text(&heading, &answer.metrics));
+        }
+    }
+}
+
+/// Prints one row per day with its status and time, then the totals.
+fn print_summary(
+    days: &[u8],
+    results: &[(InputSource, error::Result<shared::Answers>, Duration)],
+    wall: Duration,
+) {
+    println!();
+    println!("{:>3}  {:<10}  {:>12}  answers", "day", "status", "time");
+    let (mut solved, mut unknown, mut failed) = (0, 0, 0);
+    let mut total = Duration::ZERO;
+    for (day, (_, result, elapsed)) in days.iter().zip(results) {
+        total += *elapsed;
+        let (status, answers) = match result {
+            Ok(answers)
+                if answers
+                    .iter()
+                    .any(|answer| answer.status == Status::Unknown) =>
+            {
+                unknown += 1;
+                ("unknown", answers)
+            }
+            Ok(answers) => {
+                solved += 1;
+                ("solved", answers)
+            }
+            Err(error) => {
+                failed += 1;
+                (error.category(), &Vec::new())
+            }
+        };
+        let answers = answers
+            .iter()
+            .map(|answer| answer.value.as_deref().unwrap_or("?"))
+            .collect::<Vec<&str>>();
+        let row = format!(
+            "{:>3}  {:<10}  {:>12}  {}",
+            day,
+            status,
+            format!("{:.2?}", elapsed),
+            answers.join(" / ")
+        );
+        println!("{}", row.trim_end());
+    }
+
+    println!(
+        "{} solved, {} unknown, {} failed in {:.2?} ({:.2?} across days)",
+        solved, unknown, failed, wall, total
+    );
+}
+
+/// Prints a timing table for every day, returning the exit code of the first
+/// failing day (or zero).
+fn run_bench(options: &Options) -> error::Result<u8> {
+    let mut code = 0;
+    let mut results = Vec::new();
+    for day in &options.days {
+        let source = InputSource::resolve(*day, options.input.as_deref());
+        let timings = source
+            .read()
+            .map_err(|error| Error::io(&source, error))
+            .and_then(|input| (DAYS[*day as usize - 1].bench)(&input, options.part, options.runs));
+        match timings {
+            Ok(timings) => results.push((*day, timings)),
+            Err(error) => {
+                eprintln!("day {} error: {}", day, error);
+                record_failure(&mut code, &error);
+            }
+        }
+    }
+
+    println!(
+        "{:>3}  {:<6}  {:>12}  {:>12}  {:>12}",
+        "day", "phase", "min", "median", "max"
+    );
+    for (day, timings) in &results {
+        for timing in timings {
+            println!(
+                "{:>3}  {:<6}  {:>12}  {:>12}  {:>12}",
+                day,
+                timing.phase.to_string(),
+                format!("{:.2?}", timing.min),
+                format!("{:.2?}", timing.median),
+                format!("{:.2?}", timing.max),
+            );
+        }
+    }
+
+    if let Some(output) = &options.output {
+        std::fs::write(output, bench::to_tsv(&results))
+            .map_err(|error| Error::io(output, error))?;
+    }
+
+    Ok(code)
+}
+
+/// Solves one day on every input matching the pattern, returning the exit
+/// code of the first failing input (or zero).
+fn run_batch(options: &Options) -> error::Result<u8> {
+    let day = options.days[0];
+    let pattern = options.pattern.as_deref().expect("batch has a pattern");
+    let inputs = batch::expand(pattern)?;
+    let entries = (DAYS[day as usize - 1].batch)(&inputs, options.part, options.jobs);
+    print!("{}", batch::report(day, &entries, options.format));
+    Ok(batch::exit_code(&entries))
+}
+
+/// Plays back (or dumps) the frames the selected day records, then prints its
+/// answers.
+fn run_visualize(options: &Options) -> error::Result<u8> {
+    let day = options.days[0];
+    let source = InputSource::resolve(day, options.input.as_deref());
+    let (answers, recording) =
+        (DAYS[day as usize - 1].visualize)(&source, options.part, options.max_frames)?;
+    match &options.frames {
+        Some(directory) => {
+            let paths = visualize::dump(&recording, Path::new(directory))?;
+            println!("wrote {} frames to {}", paths.len(), directory);
+        }
+        None => visualize::play(
+            &recording,
+            options.playback,
+            &mut io::stdout().lock(),
+            &mut io::stdin().lock(),
+        )
+        .map_err(|error| Error::io("stdout", error))?,
+    }
+    print_answers(day, &answers, false);
+
+    Ok(if answers.iter().any(|answer| answer.value.is_none()) {
+        EXIT_BUDGET_EXCEEDED
+    } else {
+        0
+    })
+}
+
+/// Checks every matching entry of the answers file, returning `EXIT_FAILURE`
+/// unless all of them passed.
+fn run_verify(options: &Options) -> error::Result<u8> {
+    let path = Path::new(options.answers.as_deref().unwrap_or(DEFAULT_ANSWERS));
+    let contents = shared::read_string(path).map_err(|error| Error::io(path.display(), error))?;
+    let parts = DAYS.iter().map(|day| day.parts).collect::<Vec<u8>>();
+    let expectations = verify::parse_answers(&contents, &parts)
+        .map_err(|error| error.in_file(path.display().to_string()))?;
+    let directory = path.parent().unwrap_or(Path::new("."));
+
+    let (mut passed, mut failed) = (0, 0);
+    for expectation in expectations {
+        let day = expectation.day;
+        if !options.days.contains(&day) || options.part.is_some_and(|part| part != expectation.part)
+        {
+            continue;
+        }
+
+        let source = InputSource::Path(directory.join(&expectation.input));
+        let answers = (DAYS[day as usize - 1].run)(&source, Some(expectation.part));
+        let outcome = verify::check(&expectation, answers);
+        println!("{}", verify::report(&expectation, &outcome));
+        if outcome == Outcome::Pass {
+            passed += 1;
+        } else {
+            failed += 1;
+        }
+    }
+
+    println!("{} passed, {} failed", passed, failed);
+    Ok(if failed == 0 { 0 } else { EXIT_FAILURE })
+}
+
+/// Writes generated input for every day, to stdout or to `<output>/<day>.txt`.
+fn run_generate(options: &Options) -> error::Result<u8> {
+    // Every day is generated from the seed itself, so a day's input does not
+    // depend on which other days were selected or where it is written.
+    let generate =
+        |day: u8| (DAYS[day as usize - 1].generate)(&mut Rng::new(options.seed), options.size);
+    let Some(directory) = &options.output else {
+        print!("{}", generate(options.days[0]));
+        return Ok(0);
+    };
+
+    let directory = Path::new(directory);
+    std::fs::create_dir_all(directory).map_err(|error| Error::io(directory.display(), error))?;
+    for day in &options.days {
+        let path = directory.join(format!("{}.txt", day));
+        std::fs::write(&path, generate(*day)).map_err(|error| Error::io(path.display(), error))?;
+    }
+
+    Ok(0)
+}
+
+/// Downloads every day's missing input to where `run` looks for it.
+fn run_fetch(options: &Options) -> error::Result<u8> {
+    let client = Client::from_env(options.base_url.as_deref())?;
+    for day in &options.days {
+        let InputSource::Path(path) = InputSource::resolve(*day, options.input.as_deref()) else {
+            return Err(usage("fetch needs a file to write to"));
+        };
+        if client.fetch_input(*day, &path, options.force)? {
+            println!("day {}: saved {}", day, path.display());
+        } else {
+            println!("day {}: {} already exists", day, path.display());
+        }
+    }
+
+    Ok(0)
+}
+
+/// Submits one answer, returning `EXIT_FAILURE` unless it was accepted.
+fn run_submit(options: &Options) -> error::Result<u8> {
+    let day = options.days[0];
+    let part = options
+        .part
+        .ok_or_else(|| usage("submit requires --part"))?;
+    let answer = match &options.answer {
+        Some(answer) => answer.clone(),
+        None => {
+            let source = InputSource::resolve(day, options.input.as_deref());
+            let answers = (DAYS[day as usize - 1].run)(&source, Some(part))?;
+            let answer = answers
+                .into_iter()
+                .next()
+                .ok_or_else(|| usage(format!("day {} part {} gave no answer", day, part)))?;
+            match (answer.value, answer.status) {
+                (Some(value), Status::Solved) => value,
+                (Some(_), Status::Unknown) => {
+                    return Err(usage("the answer is a guess; pass --answer to submit it"));
+                }
+                (None, _) => {
+                    return Err(Error::BudgetExceeded(format!(
+                        "day {} part {} has no answer to submit",
+                        day, part
+                    )));
+                }
+            }
+        }
+    };
+
+    let client = Client::from_env(options.base_url.as_deref())?;
+    let verdict = client.submit(day, part, &answer)?;
+    println!("day {} part {}: {} is {}", day, part, answer, verdict);
+    Ok(if verdict == Verdict::Correct {
+        0
+    } else {
+        EXIT_FAILURE
+    })
+}
+
+enum Command {
+    Run,
+    Bench,
+    Batch,
+    Visualize,
+    Verify,
+    Generate,
+    Fetch,
+    Submit,
+}
+
+struct Options {
+    command: Command,
+    days: Vec<u8>,
+    pattern: Option<String>,
+    part: Option<u8>,
+    input: Option<String>,
+    runs: usize,
+    output: Option<String>,
+    answers: Option<String>,
+    format: Format,
+    stats: bool,
+    watch: bool,
+    jobs: usize,
+    budget: Budget,
+    mode: num::Mode,
+    size: usize,
+    seed: u64,
+    force: bool,
+    answer: Option<String>,
+    base_url: Option<String>,
+    playback: Playback,
+    frames: Option<String>,
+    max_frames: usize,
+}
+
+fn parse_args(args: &[String]) -> error::Result<Options> {
+    let mut args = args.iter();
+    let command = match args.next().map(String::as_str) {
+        Some("run") => Command::Run,
+        Some("bench") => Command::Bench,
+        Some("batch") => Command::Batch,
+        Some("visualize") => Command::Visualize,
+        Some("verify") => Command::Verify,
+        Some("generate") => Command::Generate,
+        Some("fetch") => Command::Fetch,
+        Some("submit") => Command::Submit,
+        Some(command) => return Err(usage(format!("unknown command: {}", command))),
+        None => return Err(usage("missing command")),
+    };
+
+    let days = match args.next() {
+        Some(days) => parse_days(days)?,
+        None => return Err(usage("missing day")),
+    };
+
+    let mut options = Options {
+        command,
+        days,
+        pattern: None,
+        part: None,
+        input: None,
+        runs: DEFAULT_RUNS,
+        output: None,
+        answers: None,
+        format: Format::Text,
+        stats: false,
+        watch: false,
+        jobs: pool::default_jobs(),
+        budget: Budget {
+            steps: Some(budget::DEFAULT_STEPS),
+            ..Budget::default()
+        },
+        mode: num::Mode::Checked,
+        size: DEFAULT_SIZE,
+        seed: 0,
+        force: false,
+        answer: None,
+        base_url: None,
+        playback: Playback::default(),
+        frames: None,
+        max_frames: visualize::DEFAULT_FRAME_LIMIT,
+    };
+    while let Some(arg) = args.next() {
+        let is_bench = matches!(options.command, Command::Bench);
+        let is_verify = matches!(options.command, Command::Verify);
+        let is_run = matches!(options.command, Command::Run);
+        let is_generate = matches!(options.command, Command::Generate);
+        let is_fetch = matches!(options.command, Command::Fetch);
+        let is_submit = matches!(options.command, Command::Submit);
+        let is_batch = matches!(options.command, Command::Batch);
+        let is_visualize = matches!(options.command, Command::Visualize);
+        let solves = is_run || is_bench || is_batch || is_visualize || is_verify || is_submit;
+        match arg.as_str() {
+            "--size" if is_generate => {
+                let value = next_value(&mut args, arg)?;
+                options.size = value
+                    .parse()
+                    .map_err(|_| usage(format!("invalid size: {}", value)))?;
+            }
+            "--seed" if is_generate => {
+                let value = next_value(&mut args, arg)?;
+                options.seed = value
+                    .parse()
+                    .map_err(|_| usage(format!("invalid seed: {}", value)))?;
+            }
+            "--part" if solves => options.part = Some(parse_part(next_value(&mut args, arg)?)?),
+            "--input" if is_run || is_bench || is_visualize || is_fetch || is_submit => {
+                options.input = Some(next_value(&mut args, arg)?.to_string())
+            }
+            "--runs" if is_bench => {
+                let value = next_value(&mut args, arg)?;
+                options.runs = match value.parse::<usize>() {
+                    Ok(runs) if runs > 0 => runs,
+                    _ => return Err(usage(format!("invalid run count: {}", value))),
+                };
+            }
+            "--output" if is_bench || is_generate => {
+                options.output = Some(next_value(&mut args, arg)?.to_string())
+            }
+            "--format" if is_run || is_batch => {
+                options.format = next_value(&mut args, arg)?.parse()?
+            }
+            "--stats" if is_run => options.stats = true,
+            #[cfg(target_os = "linux")]
+            "--watch" if is_run => options.watch = true,
+            #[cfg(not(target_os = "linux"))]
+            "--watch" if is_run => return Err(usage("--watch is only supported on Linux")),
+            "--jobs" if is_run || is_batch => {
+                let value = next_value(&mut args, arg)?;
+                options.jobs = match value.parse::<usize>() {
+                    Ok(jobs) if jobs > 0 => jobs,
+                    _ => return Err(usage(format!("invalid job count: {}", value))),
+                };
+            }
+            "--timeout" if solves => {
+                let value = next_value(&mut args, arg)?;
+                options.budget.time = Some(
+                    budget::parse_seconds(value)
+                        .ok_or_else(|| usage(format!("invalid timeout: {}", value)))?,
+                );
+            }
+            "--max-steps" if solves => {
+                let value = next_value(&mut args, arg)?;
+                options.budget.steps = budget::parse_steps(value)
+                    .ok_or_else(|| usage(format!("invalid step limit: {}", value)))?;
+            }
+            "--arithmetic" if solves => options.mode = next_value(&mut args, arg)?.parse()?,
+            "--wide" if solves => options.mode = num::Mode::Wide,
+            "--fps" if is_visualize => {
+                let value = next_value(&mut args, arg)?;
+                options.playback.fps = match value.parse::<f64>() {
+                    Ok(fps) if fps.is_finite() && fps >= 0.0 => fps,
+                    _ => return Err(usage(format!("invalid frame rate: {}", value))),
+                };
+            }
+            "--frames" if is_visualize => {
+                options.frames = Some(next_value(&mut args, arg)?.to_string())
+            }
+            "--max-frames" if is_visualize => {
+                let value = next_value(&mut args, arg)?;
+                options.max_frames = match value.parse::<usize>() {
+                    Ok(frames) if frames > 0 => frames,
+                    _ => return Err(usage(format!("invalid frame limit: {}", value))),
+                };
+            }
+            "--no-color" if is_visualize => options.playback.color = false,
+            "--force" if is_fetch => options.force = true,
+            "--answer" if is_submit => {
+                options.answer = Some(next_value(&mut args, arg)?.to_string())
+            }
+            "--base-url" if is_fetch || is_submit => {
+                options.base_url = Some(next_value(&mut args, arg)?.to_string())
+            }
+            "--answers" if is_verify => {
+                options.answers = Some(next_value(&mut args, arg)?.to_string())
+            }
+            _ if is_batch && options.pattern.is_none() && !arg.starts_with("--") => {
+                options.pattern = Some(arg.to_string())
+            }
+            _ => return Err(usage(format!("unknown argument: {}", arg))),
+        }
+    }
+
+    // The final day has only one part. Asking for its part 2 by name is an
+    // error, but `all --part 2` just leaves it out.
+    if let Some(part) = options.part {
+        let has_part = |day: &u8| part <= DAYS[*day as usize - 1].parts;
+        match options.days.as_slice() {
+            [day] if !has_part(day) => {
+                return Err(usage(format!("day {} has no part {}", day, part)));
+            }
+            _ => options.days.retain(has_part),
+        }
+    }
+    let is_generate = matches!(options.command, Command::Generate);
+    if is_generate && options.output.is_none() && options.days.len() > 1 {
+        return Err(usage("generating several days requires --output"));
+    }
+    if options.input.is_some() && options.days.len() > 1 {
+        return Err(usage("--input requires a single day"));
+    }
+    if matches!(options.command, Command::Submit) && options.days.len() > 1 {
+        return Err(usage("submit requires a single day"));
+    }
+    if matches!(options.command, Command::Visualize) && options.days.len() > 1 {
+        return Err(usage("visualize requires a single day"));
+    }
+    if matches!(options.command, Command::Batch) {
+        if options.days.len() > 1 {
+            return Err(usage("batch requires a single day"));
+        }
+        if options.pattern.is_none() {
+            return Err(usage("missing inputs for batch"));
+        }
+    }
+    if options.watch && options.days.len() > 1 {
+        return Err(usage("--watch requires a single day"));
+    }
+    if options.watch && options.format == Format::Json {
+        return Err(usage("--watch only prints text"));
+    }
+
+    Ok(options)
+}
+
+fn next_value<'a>(
+    args: &mut impl Iterator<Item = &'a String>,
+    flag: &str,
+) -> error::Result<&'a str> {
+    args.next()
+        .map(String::as_str)
+        .ok_or_else(|| usage(format!("missing value for {}", flag)))
+}
+
+/// Parses `all` or a comma-separated list of days and `first-last` ranges into
+/// sorted, distinct days.
+fn parse_days(days: &str) -> error::Result<Vec<u8>> {
+    if days == "all" {
+        return Ok((1..=DAYS.len() as u8).collect());
+    }
+
+    let mut selected = Vec::new();
+    for selector in days.split(',') {
+        match selector.split_once('-') {
+            Some((first, last)) => {
+                let (first, last) = (parse_day(first)?, parse_day(last)?);
+                if first > last {
+                    return Err(usage(format!("invalid day range: {}", selector)));
+                }
+                selected.extend(first..=last);
+            }
+            None => selected.push(parse_day(selector)?),
+        }
+    }
+    selected.sort();
+    selected.dedup();
+
+    Ok(selected)
+}
+
+fn parse_day(day: &str) -> error::Result<u8> {
+    match day.parse::<u8>() {
+        Ok(day) if (1..=DAYS.len() as u8).contains(&day) => Ok(day),
+        _ => Err(usage(format!("invalid day: {}", day))),
+    }
+}
+
+fn parse_part(part: &str) -> error::Result<u8> {
+    match part {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(usage(format!("invalid part: {}", part))),
+    }
+}
+
+fn usage(message: impl Into<String>) -> Error {
+    Error::Usage(message.into())
+}
@@ -0,0 +1,281 @@
+use crate::{
+    Answers, InputSource, Solution, Status,
+    error::{self, EXIT_BUDGET_EXCEEDED, Error},
+    output::{self, Format},
+    pool, run,
+};
+use std::{
+    fmt::Write,
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+/// One input of a batch and how solving it went.
+#[derive(Debug)]
+pub struct Entry {
+    pub input: InputSource,
+    pub result: error::Result<Answers>,
+    /// Reading, parsing and solving together.
+    pub elapsed: Duration,
+}
+
+/// Input files selected by `pattern`: every file in a directory, a single
+/// file, or the files matching a glob whose wildcards (`*` and `?`) are all
+/// in its last component, such as `inputs/12-*.txt`. Hidden files are skipped
+/// and the result is sorted.
+pub fn expand(pattern: &str) -> error::Result<Vec<PathBuf>> {
+    let path = Path::new(pattern);
+    let is_glob = |part: &str| part.contains(['*', '?']);
+    let (directory, name) = if path.is_dir() {
+        (path, "*")
+    } else {
+        match path.file_name().and_then(|name| name.to_str()) {
+            Some(name) if is_glob(name) => {
+                let directory = path
+                    .parent()
+                    .filter(|parent| !parent.as_os_str().is_empty());
+                (directory.unwrap_or(Path::new(".")), name)
+            }
+            _ => return Ok(vec![path.to_path_buf()]),
+        }
+    };
+    if is_glob(&directory.to_string_lossy()) {
+        return Err(Error::Usage(format!(
+            "wildcards are only supported in file names: {}",
+            pattern
+        )));
+    }
+
+    let entries = fs::read_dir(directory).map_err(|error| Error::io(directory.display(), error))?;
+    let mut inputs = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|error| Error::io(directory.display(), error))?;
+        let file_name = entry.file_name();
+        let Some(file_name) = file_name.to_str() else {
+            continue;
+        };
+        if !file_name.starts_with('.') && glob_match(name, file_name) && entry.path().is_file() {
+            inputs.push(entry.path());
+        }
+    }
+    if inputs.is_empty() {
+        return Err(Error::Usage(format!("no inputs match {}", pattern)));
+    }
+    inputs.sort();
+
+    Ok(inputs)
+}
+
+/// Whether `name` matches `pattern`, where `*` stands for any run of
+/// characters and `?` for exactly one.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let (pattern, name) = (
+        pattern.chars().collect::<Vec<char>>(),
+        name.chars().collect::<Vec<char>>(),
+    );
+    let (mut p, mut n) = (0, 0);
+    // Where to resume after the last `*`: its position and the name
+    // position it currently stands in for up to.
+    let mut star = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&expected) if expected == '?' || expected == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    star = Some((star_p, star_n + 1));
+                    p = star_p + 1;
+                    n = star_n + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|rest| *rest == '*')
+}
+
+/// Runs `work`, reporting a panic as `Error::Panic` instead of unwinding.
+pub fn isolate<T>(work: impl FnOnce() -> error::Result<T>) -> error::Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(work)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Err(Error::Panic(message))
+    })
+}
+
+/// Solves every input on up to `jobs` threads. An input that fails or
+/// panics only fails its own entry.
+pub fn run_batch<S: Solution>(inputs: &[PathBuf], part: Option<u8>, jobs: usize) -> Vec<Entry> {
+    pool::map(inputs, jobs, |path| {
+        let input = InputSource::Path(path.clone());
+        let start = Instant::now();
+        let result = isolate(|| run::<S>(&input, part));
+        Entry {
+            input,
+            result,
+            elapsed: start.elapsed(),
+        }
+    })
+}
+
+/// Exit code for a batch: that of the first failing entry, or
+/// `EXIT_BUDGET_EXCEEDED` if a part ran out of budget, or zero.
+pub fn exit_code(entries: &[Entry]) -> u8 {
+    let failed = entries.iter().find_map(|entry| entry.result.as_ref().err());
+    let exhausted = entries.iter().any(|entry| {
+        entry
+            .result
+            .as_ref()
+            .is_ok_and(|answers| answers.iter().any(|answer| answer.value.is_none()))
+    });
+    match failed {
+        Some(error) => error.exit_code(),
+        None if exhausted => EXIT_BUDGET_EXCEEDED,
+        None => 0,
+    }
+}
+
+/// The batch as a `table`, or as JSON records (see `output::json_records`)
+/// for every input.
+pub fn report(day: u8, entries: &[Entry], format: Format) -> String {
+    match format {
+        Format::Text => table(entries),
+        Format::Json => {
+            let records = entries
+                .iter()
+                .flat_map(|entry| output::json_records(day, &entry.input, &entry.result, false))
+                .collect::<Vec<String>>();
+            format!("{}\n", output::json_array(&records))
+        }
+    }
+}
+
+/// One row per input with its status, time and answers (or the first line
+/// of its error), then the totals.
+pub fn table(entries: &[Entry]) -> String {
+    let names = entries
+        .iter()
+        .map(|entry| entry.input.to_string())
+        .collect::<Vec<String>>();
+    let width = names.iter().map(String::len).max().unwrap_or(0).max(4);
+
+    let mut text = format!(
+        "{:<width$}  {:<10}  {:>12}  answers\n",
+        "file",
+        "status",
+        "time",
+        width = width
+    );
+    let (mut solved, mut unknown, mut failed) = (0, 0, 0);
+    for (name, entry) in names.iter().zip(entries) {
+        let (status, details) = match &entry.result {
+            Ok(answers) => {
+                let values = answers
+                    .iter()
+                    .map(|answer| answer.value.as_deref().unwrap_or("?"))
+                    .collect::<Vec<&str>>()
+                    .join(" / ");
+                let status = if answers
+                    .iter()
+                    .any(|answer| answer.status == Status::Unknown)
+                {
+                    unknown += 1;
+                    "unknown"
+                } else {
+                    solved += 1;
+                    "solved"
+                };
+                (status, values)
+            }
+            Err(error) => {
+                failed += 1;
+                // Parse errors quote the input below their first line.
+                let message = error.to_string();
+                let first_line = message.lines().next().unwrap_or("").to_string();
+                (error.category(), first_line)
+            }
+        };
+        let row = format!(
+            "{:<width$}  {:<10}  {:>12}  {}",
+            name,
+            status,
+            format!("{:.2?}", entry.elapsed),
+            details,
+            width = width
+        );
+        let _ = writeln!(text, "{}", row.trim_end());
+    }
+    let _ = writeln!(
+        text,
+        "{} solved, {} unknown, {} failed",
+        solved, unknown, failed
+    );
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_1::Day1;
+
+    #[test]
+    fn globs() {
+        assert!(glob_match("*.txt", "1.txt"));
+        assert!(glob_match("12-*-?.txt", "12-alice-2.txt"));
+        assert!(glob_match("*a*b", "xaxxab"));
+        assert!(!glob_match("*.txt", "1.txt.bak"));
+        assert!(!glob_match("?.txt", "12.txt"));
+        assert!(glob_match("*", ""));
+    }
+
+    #[test]
+    fn isolates_failures() {
+        let directory = std::env::temp_dir().join(format!("aoc-batch-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("a.txt"), "L68\nL30\nR48\n").unwrap();
+        fs::write(directory.join("b.txt"), "L68\nX30\n").unwrap();
+        fs::write(directory.join("c.in"), "R1\n").unwrap();
+        fs::write(directory.join(".hidden.txt"), "R1\n").unwrap();
+
+        let pattern = directory.join("*.txt");
+        let inputs = expand(pattern.to_str().unwrap()).unwrap();
+        let names = inputs
+            .iter()
+            .map(|input| input.file_name().unwrap().to_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["a.txt", "b.txt"]);
+        assert_eq!(expand(directory.to_str().unwrap()).unwrap().len(), 3);
+
+        let entries = run_batch::<Day1>(&inputs, None, 2);
+        assert!(entries[0].result.is_ok());
+        assert!(matches!(entries[1].result, Err(Error::Parse(_))));
+        assert_eq!(exit_code(&entries), 4);
+        let table = table(&entries);
+        assert!(
+            table.ends_with("1 solved, 0 unknown, 1 failed\n"),
+            "{}",
+            table
+        );
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn catches_panics() {
+        let result: error::Result<()> = isolate(|| panic!("index {} out of bounds", 3));
+        assert!(matches!(result, Err(Error::Panic(message)) if message == "index 3 out of bounds"));
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{Solution, error, selected_parts, solve_part};
+use std::{
+    fmt::{self, Display},
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+/// Phase of a solution that gets timed separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part(u8),
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part(part) => write!(f, "part {}", part),
+        }
+    }
+}
+
+/// Wall-clock summary over every run of one phase.
+#[derive(Debug, Clone)]
+pub struct Timing {
+    pub phase: Phase,
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timing {
+    fn from_samples(phase: Phase, mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        Self {
+            phase,
+            runs: samples.len(),
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Times parsing and the requested part (or every part if `None`) of `input`
+/// over `runs` runs each. Panics if `runs` is zero.
+pub fn bench<S: Solution>(
+    input: &str,
+    part: Option<u8>,
+    runs: usize,
+) -> error::Result<Vec<Timing>> {
+    assert!(runs > 0, "at least one run is required");
+
+    let mut parse_samples = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let start = Instant::now();
+        black_box(S::parse(black_box(input))?);
+        parse_samples.push(start.elapsed());
+    }
+    let mut timings = vec![Timing::from_samples(Phase::Parse, parse_samples)];
+
+    let parsed = S::parse(input)?;
+    for current in selected_parts::<S>(part) {
+        let mut samples = Vec::with_capacity(runs);
+        for _ in 0..runs {
+            let start = Instant::now();
+            black_box(solve_part::<S>(black_box(&parsed), current)?);
+            samples.push(start.elapsed());
+        }
+        timings.push(Timing::from_samples(Phase::Part(current), samples));
+    }
+
+    Ok(timings)
+}
+
+/// Renders timings as tab-separated values with nanosecond durations, one row
+/// per day and phase, for tracking results across commits.
+pub fn to_tsv(results: &[(u8, Vec<Timing>)]) -> String {
+    let mut tsv = String::from("day\tphase\truns\tmin_ns\tmedian_ns\tmax_ns\n");
+    for (day, timings) in results {
+        for timing in timings {
+            tsv.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{}\t{}\n",
+                day,
+                timing.phase,
+                timing.runs,
+                timing.min.as_nanos(),
+                timing.median.as_nanos(),
+                timing.max.as_nanos(),
+            ));
+        }
+    }
+
+    tsv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(samples: &[u64]) -> Vec<Duration> {
+        samples.iter().copied().map(Duration::from_micros).collect()
+    }
+
+    #[test]
+    fn summarizes_samples() {
+        let timing = Timing::from_samples(Phase::Part(2), micros(&[30, 10, 50, 20, 40]));
+        assert_eq!((timing.phase, timing.runs), (Phase::Part(2), 5));
+        assert_eq!(
+            (timing.min, timing.median, timing.max),
+            (
+                Duration::from_micros(10),
+                Duration::from_micros(30),
+                Duration::from_micros(50)
+            )
+        );
+
+        // With an even number of runs the upper of the two middle samples is
+        // the median.
+        let timing = Timing::from_samples(Phase::Parse, micros(&[4, 1, 3, 2]));
+        assert_eq!(timing.median, Duration::from_micros(3));
+
+        let timing = Timing::from_samples(Phase::Parse, micros(&[7]));
+        let seven = Duration::from_micros(7);
+        assert_eq!(
+            (timing.min, timing.median, timing.max),
+            (seven, seven, seven)
+        );
+    }
+
+    #[test]
+    fn renders_tsv() {
+        let results = [
+            (
+                3,
+                vec![
+                    Timing::from_samples(Phase::Parse, micros(&[2, 1, 3])),
+                    Timing::from_samples(Phase::Part(1), micros(&[5, 4])),
+                ],
+            ),
+            (12, vec![]),
+        ];
+        assert_eq!(
+            to_tsv(&results),
+            "day\tphase\truns\tmin_ns\tmedian_ns\tmax_ns\n\
+             3\tparse\t3\t1000\t2000\t3000\n\
+             3\tpart 1\t2\t4000\t5000\t5000\n"
+        );
+        assert_eq!(to_tsv(&[]), "day\tphase\truns\tmin_ns\tmedian_ns\tmax_ns\n");
+    }
+}
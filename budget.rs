@@ -0,0 +1,280 @@
+use crate::error::{self, Error};
+use std::{
+    cell::RefCell,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+/// Step limit the command-line tools apply unless told otherwise.
+pub const DEFAULT_STEPS: u64 = 10_000_000;
+
+/// How many steps pass between looks at the clock; reading it on every step
+/// would dominate tight search loops.
+const CLOCK_INTERVAL: u64 = 1024;
+
+/// Flag for stopping solvers from another thread. Clones share the flag.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Limits on the work spent on each part. Solvers poll them through `step`;
+/// a part that runs out is reported as unknown rather than failed.
+#[derive(Debug, Clone, Default)]
+pub struct Budget {
+    /// Wall-clock time allowed per part.
+    pub time: Option<Duration>,
+    /// Steps allowed per part. What a step is is up to each solver, e.g. one
+    /// pair of points for day 8; day 12 allows this many per region (see
+    /// `fresh`).
+    pub steps: Option<u64>,
+    pub cancel: CancelToken,
+}
+
+static CONFIGURED: Mutex<Option<Budget>> = Mutex::new(None);
+
+/// Sets the budget `solve_part` applies from now on, on every thread.
+pub fn configure(budget: Budget) {
+    *CONFIGURED.lock().unwrap_or_else(|error| error.into_inner()) = Some(budget);
+}
+
+/// The budget set by `configure`, or no limits at all.
+pub fn configured() -> Budget {
+    CONFIGURED
+        .lock()
+        .unwrap_or_else(|error| error.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
+
+struct Active {
+    deadline: Option<Instant>,
+    max_steps: Option<u64>,
+    steps: u64,
+    next_clock: u64,
+    cancel: CancelToken,
+    /// Whether the last failure was from running out of steps.
+    out_of_steps: bool,
+}
+
+thread_local! {
+    static ACTIVE: RefCell<Option<Active>> = const { RefCell::new(None) };
+}
+
+/// Keeps a budget armed on the current thread until dropped.
+#[must_use = "the budget is disarmed when the guard is dropped"]
+pub struct Armed(());
+
+impl Drop for Armed {
+    fn drop(&mut self) {
+        ACTIVE.set(None);
+    }
+}
+
+/// Arms `budget` on the current thread, starting its clock now.
+pub fn start(budget: &Budget) -> Armed {
+    ACTIVE.set(Some(Active {
+        deadline: budget.time.map(|time| Instant::now() + time),
+        max_steps: budget.steps,
+        steps: 0,
+        next_clock: CLOCK_INTERVAL,
+        cancel: budget.cancel.clone(),
+        out_of_steps: false,
+    }));
+    Armed(())
+}
+
+/// Records `steps` units of work, failing with `BudgetExceeded` once the
+/// armed budget runs out or is cancelled. Without an armed budget this never
+/// fails.
+pub fn step(steps: u64) -> error::Result<()> {
+    ACTIVE.with_borrow_mut(|active| {
+        let Some(active) = active else {
+            return Ok(());
+        };
+
+        active.steps += steps;
+        if active.cancel.is_cancelled() {
+            return Err(Error::BudgetExceeded("cancelled".to_string()));
+        }
+        if let Some(max_steps) = active.max_steps
+            && active.steps > max_steps
+        {
+            active.out_of_steps = true;
+            return Err(Error::BudgetExceeded(format!(
+                "gave up after {} steps",
+                max_steps
+            )));
+        }
+        if let Some(deadline) = active.deadline
+            && active.steps >= active.next_clock
+        {
+            active.next_clock = active.steps + CLOCK_INTERVAL;
+            if Instant::now() >= deadline {
+                return Err(Error::BudgetExceeded(format!(
+                    "gave up after {} steps at the time limit",
+                    active.steps
+                )));
+            }
+        }
+
+        Ok(())
+    })
+}
+
+/// Checks the clock and cancellation without recording work, for solvers
+/// that poll rarely.
+pub fn check() -> error::Result<()> {
+    ACTIVE.with_borrow_mut(|active| {
+        if let Some(active) = active {
+            active.next_clock = active.steps;
+        }
+    });
+    step(0)
+}
+
+/// Runs `f` with a step count of its own, starting from zero, under the same
+/// time limit and cancellation. Gives `None` if `f` runs out of steps, which
+/// suits solvers made of independent pieces of work that should each get the
+/// whole step limit; running out of time or being cancelled still fails with
+/// `BudgetExceeded`. Steps taken by `f` do not count towards the caller's.
+pub fn fresh<T>(f: impl FnOnce() -> error::Result<T>) -> error::Result<Option<T>> {
+    let outer = ACTIVE.with_borrow_mut(|active| {
+        active
+            .as_mut()
+            .map(|active| std::mem::replace(&mut active.steps, 0))
+    });
+
+    let result = f();
+    let out_of_steps = ACTIVE.with_borrow_mut(|active| match (active, outer) {
+        (Some(active), Some(outer)) => {
+            active.steps = outer;
+            active.next_clock = outer + CLOCK_INTERVAL;
+            std::mem::take(&mut active.out_of_steps)
+        }
+        _ => false,
+    });
+    match result {
+        Err(Error::BudgetExceeded(_)) if out_of_steps => Ok(None),
+        result => result.map(Some),
+    }
+}
+
+/// Parses a time limit in (possibly fractional) seconds.
+pub fn parse_seconds(value: &str) -> Option<Duration> {
+    value
+        .parse::<f64>()
+        .ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+}
+
+/// Parses a step limit: a count, or `none` for no limit.
+pub fn parse_steps(value: &str) -> Option<Option<u64>> {
+    match value {
+        "none" => Some(None),
+        _ => value.parse().ok().map(Some),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unarmed_never_fails() {
+        assert!(step(u64::MAX / 2).is_ok());
+    }
+
+    #[test]
+    fn step_limit() {
+        let budget = Budget {
+            steps: Some(10),
+            ..Budget::default()
+        };
+        let armed = start(&budget);
+        assert!(step(10).is_ok());
+        assert!(matches!(step(1), Err(Error::BudgetExceeded(_))));
+
+        drop(armed);
+        assert!(step(1).is_ok());
+    }
+
+    #[test]
+    fn time_limit() {
+        let budget = Budget {
+            time: Some(Duration::ZERO),
+            ..Budget::default()
+        };
+        let _armed = start(&budget);
+        // The clock is only read every `CLOCK_INTERVAL` steps.
+        assert!(step(1).is_ok());
+        assert!(step(CLOCK_INTERVAL).is_err());
+        assert!(check().is_err());
+    }
+
+    #[test]
+    fn cancellation_from_another_thread() {
+        let budget = Budget::default();
+        let _armed = start(&budget);
+        assert!(check().is_ok());
+
+        let token = budget.cancel.clone();
+        std::thread::spawn(move || token.cancel()).join().unwrap();
+        assert!(budget.cancel.is_cancelled());
+        assert!(step(1).is_err());
+    }
+
+    #[test]
+    fn fresh_step_counts() {
+        let budget = Budget {
+            steps: Some(10),
+            ..Budget::default()
+        };
+        let _armed = start(&budget);
+        assert!(step(8).is_ok());
+        assert_eq!(fresh(|| step(10)).unwrap(), Some(()));
+        assert_eq!(fresh(|| step(11)).unwrap(), None);
+        assert!(step(2).is_ok());
+        assert!(step(1).is_err());
+    }
+
+    #[test]
+    fn fresh_step_counts_share_the_clock() {
+        assert_eq!(fresh(|| step(u64::MAX / 2)).unwrap(), Some(()));
+
+        let budget = Budget {
+            time: Some(Duration::ZERO),
+            ..Budget::default()
+        };
+        let _armed = start(&budget);
+        assert!(matches!(
+            fresh(|| step(CLOCK_INTERVAL)),
+            Err(Error::BudgetExceeded(_))
+        ));
+    }
+
+    #[test]
+    fn parses_limits() {
+        assert_eq!(parse_seconds("1.5"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_seconds("-1"), None);
+        assert_eq!(parse_seconds("soon"), None);
+        assert_eq!(parse_steps("none"), Some(None));
+        assert_eq!(parse_steps("5"), Some(Some(5)));
+        assert_eq!(parse_steps("-5"), None);
+    }
+}
@@ -0,0 +1,381 @@
+use crate::error::{self, Error};
+use std::{
+    env,
+    fmt::{self, Display},
+    fs, io,
+    path::Path,
+    time::Duration,
+};
+
+/// Environment variable overriding the site to talk to, e.g. a mock server.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable holding the value of the site's `session` cookie.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+pub const YEAR: u16 = 2025;
+
+const USER_AGENT: &str = "advent-2025 solutions runner";
+
+/// Talks to the puzzle site on behalf of one logged-in user.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+/// Hint given along with a wrong answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong {
+        hint: Option<Hint>,
+    },
+    /// Answers are rate limited; nothing was checked.
+    TooRecent {
+        wait: Option<Duration>,
+    },
+    /// The part is already solved or not yet unlocked.
+    WrongLevel,
+    /// A response this client does not know, as plain text.
+    Unrecognized(String),
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Wrong { hint: None } => write!(f, "wrong"),
+            Verdict::Wrong {
+                hint: Some(Hint::TooHigh),
+            } => write!(f, "wrong (too high)"),
+            Verdict::Wrong {
+                hint: Some(Hint::TooLow),
+            } => write!(f, "wrong (too low)"),
+            Verdict::TooRecent { wait: None } => write!(f, "submitted too recently"),
+            Verdict::TooRecent { wait: Some(wait) } => {
+                write!(f, "submitted too recently; wait {}s", wait.as_secs())
+            }
+            Verdict::WrongLevel => write!(f, "part already solved or still locked"),
+            Verdict::Unrecognized(text) => write!(f, "unrecognized response: {}", text),
+        }
+    }
+}
+
+impl Client {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        let config = ureq::Agent::config_builder()
+            .user_agent(USER_AGENT)
+            .http_status_as_error(false)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build();
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+            agent: config.into(),
+        }
+    }
+
+    /// Client for `base_url`, else `AOC_BASE_URL`, else the real site, with
+    /// the session from `AOC_SESSION`.
+    pub fn from_env(base_url: Option<&str>) -> error::Result<Self> {
+        let base_url = base_url
+            .map(str::to_string)
+            .or_else(|| env::var(BASE_URL_VAR).ok())
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        let session = env::var(SESSION_VAR)
+            .ok()
+            .filter(|session| !session.trim().is_empty())
+            .ok_or_else(|| Error::Usage(format!("{} is not set", SESSION_VAR)))?;
+
+        Ok(Self::new(base_url, session.trim()))
+    }
+
+    fn url(&self, day: u8, endpoint: &str) -> String {
+        format!("{}/{}/day/{}/{}", self.base_url, YEAR, day, endpoint)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// Downloads the puzzle input for `day`.
+    pub fn input(&self, day: u8) -> error::Result<String> {
+        let url = self.url(day, "input");
+        let response = self.agent.get(&url).header("Cookie", self.cookie()).call();
+        read_body(&url, response)
+    }
+
+    /// Downloads the input for `day` to `path` unless it is already there (or
+    /// `force` is set). Returns whether it was downloaded.
+    pub fn fetch_input(&self, day: u8, path: &Path, force: bool) -> error::Result<bool> {
+        if !force && fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0) {
+            return Ok(false);
+        }
+
+        let input = self.input(day)?;
+        if let Some(directory) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(directory).map_err(|error| Error::io(directory.display(), error))?;
+        }
+        fs::write(path, input).map_err(|error| Error::io(path.display(), error))?;
+
+        Ok(true)
+    }
+
+    /// Submits `answer` for one part of `day`.
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> error::Result<Verdict> {
+        let url = self.url(day, "answer");
+        let response = self
+            .agent
+            .post(&url)
+            .header("Cookie", self.cookie())
+            .send_form([("level", part.to_string()), ("answer", answer.to_string())]);
+        Ok(parse_response(&read_body(&url, response)?))
+    }
+}
+
+/// Body of a successful response; failures (including non-200 statuses) are
+/// reported as I/O errors on `url`.
+fn read_body(
+    url: &str,
+    response: Result<ureq::http::Response<ureq::Body>, ureq::Error>,
+) -> error::Result<String> {
+    let failed = |message: String| Error::io(url, io::Error::other(message));
+    let mut response = response.map_err(|error| failed(error.to_string()))?;
+    let body = response
+        .body_mut()
+        .read_to_string()
+        .map_err(|error| failed(error.to_string()))?;
+    let status = response.status();
+    if !status.is_success() {
+        let reason = body.lines().next().unwrap_or("").trim();
+        return Err(failed(format!("HTTP {}: {}", status.as_u16(), reason)));
+    }
+
+    Ok(body)
+}
+
+/// Reads the verdict out of the page returned for a submitted answer.
+pub fn parse_response(page: &str) -> Verdict {
+    let text = plain_text(article(page));
+    if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        Verdict::Wrong { hint }
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::TooRecent {
+            wait: parse_wait(&text),
+        }
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unrecognized(text)
+    }
+}
+
+/// The page's `<article>`, which holds the message, or the whole page.
+fn article(page: &str) -> &str {
+    let Some(start) = page.find("<article") else {
+        return page;
+    };
+    let end = page[start..]
+        .find("</article>")
+        .map_or(page.len(), |end| start + end);
+    &page[start..end]
+}
+
+/// `html` without tags and with runs of whitespace collapsed.
+fn plain_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for character in html.chars() {
+        match character {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(character),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// The wait in "you have 1m 5s left to wait".
+fn parse_wait(text: &str) -> Option<Duration> {
+    let start = text.find("you have ")? + "you have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+    let mut seconds = 0;
+    for amount in text[start..end].split_whitespace() {
+        let (value, unit) = amount.split_at(amount.len().checked_sub(1)?);
+        let value = value.parse::<u64>().ok()?;
+        seconds += match unit {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+    }
+
+    Some(Duration::from_secs(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Mock server answering one connection per canned `(status, body)`,
+    /// returning the requests it received.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, body)| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(&stream);
+                    let mut request = String::new();
+                    // Headers end with an empty line.
+                    while reader.read_line(&mut request).unwrap() > 2 {}
+                    let length = request
+                        .lines()
+                        .find_map(|line| {
+                            let (name, value) = line.split_once(':')?;
+                            name.eq_ignore_ascii_case("content-length")
+                                .then(|| value.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    let mut body_bytes = vec![0; length];
+                    reader.read_exact(&mut body_bytes).unwrap();
+                    request.push_str(&String::from_utf8(body_bytes).unwrap());
+
+                    write!(
+                        stream,
+                        "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    )
+                    .unwrap();
+                    request
+                })
+                .collect()
+        });
+
+        (base_url, server)
+    }
+
+    #[test]
+    fn fetches_and_caches_input() {
+        let (base_url, server) = serve(vec![(200, "3-5\n10-14\n")]);
+        let client = Client::new(base_url, "secret");
+        let directory = env::temp_dir().join(format!("aoc-client-{}", std::process::id()));
+        let path = directory.join("inputs/5.txt");
+
+        assert!(client.fetch_input(5, &path, false).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "3-5\n10-14\n");
+        // Cached: the server only answers once.
+        assert!(!client.fetch_input(5, &path, false).unwrap());
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2025/day/5/input HTTP/1.1\r\n"));
+        assert!(
+            requests[0]
+                .to_lowercase()
+                .contains("cookie: session=secret\r\n")
+        );
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn reports_failed_downloads() {
+        let (base_url, server) = serve(vec![(
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        )]);
+        let error = Client::new(base_url, "expired").input(1).unwrap_err();
+        assert!(matches!(error, Error::Io { .. }));
+        assert!(error.to_string().contains("HTTP 400: Puzzle inputs differ"));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn submits_answers() {
+        let page = "<html><main><article><p>That's not the right answer; your answer \
+                    is too low. <a href=\"/2025/day/3\">[Return]</a></p></article></main></html>";
+        let (base_url, server) = serve(vec![(200, page)]);
+        let verdict = Client::new(base_url, "secret").submit(3, 2, "42").unwrap();
+        assert_eq!(
+            verdict,
+            Verdict::Wrong {
+                hint: Some(Hint::TooLow)
+            }
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2025/day/3/answer HTTP/1.1\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=42"));
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        let article = |text: &str| format!("<main><article><p>{}</p></article></main>", text);
+        let cases = [
+            (
+                "That's the right answer! You are <em>one gold star</em> closer.",
+                Verdict::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high.",
+                Verdict::Wrong {
+                    hint: Some(Hint::TooHigh),
+                },
+            ),
+            (
+                "That's not the right answer. If you're stuck, ...",
+                Verdict::Wrong { hint: None },
+            ),
+            (
+                "You gave an answer too recently; you have 1m 5s left to wait.",
+                Verdict::TooRecent {
+                    wait: Some(Duration::from_secs(65)),
+                },
+            ),
+            (
+                "You don't seem to be solving the right level. Did you already complete it?",
+                Verdict::WrongLevel,
+            ),
+            (
+                "Something <b>new</b>",
+                Verdict::Unrecognized("Something new".to_string()),
+            ),
+        ];
+        for (text, verdict) in cases {
+            assert_eq!(parse_response(&article(text)), verdict, "{}", text);
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use crate::{
+    Solution, error,
+    generate::Rng,
+    parse::{ParseContext, ParseError},
+};
+use std::fmt::Display;
+
+// Modulus for the dial.
+const MODULUS: i32 = 100;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<Rotation>;
+    const DAY: u8 = 1;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_rotations(input, MODULUS)
+    }
+
+    /// `size` rotations of up to 999 clicks each.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_rotations(rng, size)
+    }
+
+    fn part_1(rotations: &Self::Input) -> error::Result<impl Display> {
+        Ok(problem_1(rotations))
+    }
+
+    fn part_2(rotations: &Self::Input) -> error::Result<impl Display> {
+        Ok(problem_2(rotations))
+    }
+}
+
+pub fn problem_1(rotations: &Vec<Rotation>) -> i32 {
+    let mut state = 50;
+    let mut returns = 0;
+    for rotation in rotations {
+        rotation.apply_and_count_passing_turns(&mut state);
+        if state == 0 {
+            returns += 1;
+        }
+    }
+    returns
+}
+
+pub fn problem_2(rotations: &Vec<Rotation>) -> i32 {
+    let mut state = 50;
+    let mut returns = 0;
+    for rotation in rotations {
+        let passing_turns = rotation.apply_and_count_passing_turns(&mut state);
+        returns += passing_turns;
+    }
+    returns
+}
+
+#[derive(Debug)]
+pub enum Rotation {
+    R { modulus: i32, clicks: i32 },
+    L { modulus: i32, clicks: i32 },
+}
+
+impl Rotation {
+    pub fn apply_and_count_passing_turns(&self, state: &mut i32) -> i32 {
+        match self {
+            Rotation::R { modulus, clicks } => {
+                let old_state = *state;
+                *state = (*state + clicks).rem_euclid(*modulus);
+                let full_turns = clicks / modulus;
+                full_turns
+                    + if *state != old_state && *state < old_state {
+                        1
+                    } else {
+                        0
+                    }
+            }
+            Rotation::L { modulus, clicks } => {
+                let old_state = *state;
+                *state = (*state - clicks).rem_euclid(*modulus);
+                let full_turns = clicks / modulus;
+                // Ugly; can we simplify this?
+                full_turns
+                    + if *state != old_state
+                        && (*state == 0 || (*state > old_state && old_state != 0))
+                    {
+                        1
+                    } else {
+                        0
+                    }
+            }
+        }
+    }
+}
+
+pub fn parse_rotations(input: &str, modulus: i32) -> Result<Vec<Rotation>, ParseError> {
+    let mut rotations = Vec::new();
+    for line in input.lines() {
+        let (rotation, clicks_str) = line
+            .split_at_checked(1)
+            .filter(|(rotation, _)| !rotation.is_empty())
+            .context_at(input, line, "missing rotation")?;
+        let clicks: i32 = clicks_str
+            .parse()
+            .context_at(input, clicks_str, "invalid number")?;
+        match rotation {
+            "R" => rotations.push(Rotation::R { modulus, clicks }),
+            "L" => rotations.push(Rotation::L { modulus, clicks }),
+            _ => {
+                let message = format!("invalid rotation character: {}", rotation);
+                return Err(ParseError::at(input, rotation, message));
+            }
+        }
+    }
+    Ok(rotations)
+}
+
+fn generate_rotations(rng: &mut Rng, count: usize) -> String {
+    (0..count)
+        .map(|_| format!("{}{}\n", rng.choose(&['L', 'R']), rng.range(1..=999)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82
+";
+
+    fn right(state: i32, clicks: i32) -> (i32, i32) {
+        apply(
+            Rotation::R {
+                modulus: MODULUS,
+                clicks,
+            },
+            state,
+        )
+    }
+
+    fn left(state: i32, clicks: i32) -> (i32, i32) {
+        apply(
+            Rotation::L {
+                modulus: MODULUS,
+                clicks,
+            },
+            state,
+        )
+    }
+
+    fn apply(rotation: Rotation, mut state: i32) -> (i32, i32) {
+        let passing_turns = rotation.apply_and_count_passing_turns(&mut state);
+        (state, passing_turns)
+    }
+
+    #[test]
+    fn example() {
+        let rotations = Day1::parse(EXAMPLE).unwrap();
+        assert_eq!(problem_1(&rotations), 3);
+        assert_eq!(problem_2(&rotations), 6);
+    }
+
+    #[test]
+    fn right_rotation_counts_passes_of_zero() {
+        assert_eq!(right(50, 50), (0, 1));
+        assert_eq!(right(50, 60), (10, 1));
+        assert_eq!(right(50, 1000), (50, 10));
+        assert_eq!(right(0, 100), (0, 1));
+        assert_eq!(right(0, 5), (5, 0));
+    }
+
+    #[test]
+    fn left_rotation_counts_passes_of_zero() {
+        assert_eq!(left(50, 68), (82, 1));
+        assert_eq!(left(5, 5), (0, 1));
+        assert_eq!(left(50, 250), (0, 3));
+        assert_eq!(left(0, 100), (0, 1));
+        // Leaving zero does not count as passing it.
+        assert_eq!(left(0, 5), (95, 0));
+    }
+
+    #[test]
+    fn rejects_unknown_direction() {
+        let error = Day1::parse("R10\nU10\n").unwrap_err();
+        assert_eq!((error.line, error.column, error.length), (2, 1, 1));
+        assert_eq!(error.message, "invalid rotation character: U");
+    }
+}
@@ -0,0 +1,413 @@
+use crate::{
+    budget,
+    error::{self, Error},
+    generate::Rng,
+    graph, metrics,
+    num::{self, Integer},
+    parse::{ParseContext, ParseError},
+};
+use good_lp::{
+    Constraint, Expression, ProblemVariables, Solution, SolverModel, constraint, microlp, variable,
+};
+use std::fmt::Display;
+
+pub struct Day10;
+
+impl crate::Solution for Day10 {
+    type Input = Vec<Problem>;
+    const DAY: u8 = 10;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_problems(input)
+    }
+
+    /// `size` machines of up to ten lights. Lights and joltages come from
+    /// random button presses, so every machine is solvable.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_problems(rng, size)
+    }
+
+    fn part_1(problems: &Self::Input) -> error::Result<impl Display> {
+        num::in_mode(
+            || problem_1::<u64>(problems),
+            || problem_1::<u128>(problems),
+        )
+    }
+
+    fn part_2(problems: &Self::Input) -> error::Result<impl Display> {
+        num::in_mode(
+            || problem_2::<u64>(problems),
+            || problem_2::<u128>(problems),
+        )
+    }
+}
+
+/// Each machine is one step of the armed budget. Machines are few and can be
+/// slow to solve, so the clock is checked before each of them.
+pub fn problem_1<N: Integer>(problems: &[Problem]) -> error::Result<N> {
+    let mut total_presses = N::ZERO;
+    for (index, problem) in problems.iter().enumerate() {
+        budget::step(1)?;
+        budget::check()?;
+        let presses = problem.solve_lights().ok_or_else(|| {
+            Error::Infeasible(format!("machine {} cannot reach its lights", index + 1))
+        })?;
+        total_presses = total_presses.try_add(N::try_from_u64(presses as u64)?)?;
+    }
+
+    Ok(total_presses)
+}
+
+/// Budgeted like `problem_1`.
+pub fn problem_2<N: Integer>(problems: &[Problem]) -> error::Result<N> {
+    let mut total_presses = N::ZERO;
+    for (index, problem) in problems.iter().enumerate() {
+        budget::step(1)?;
+        budget::check()?;
+        let presses = problem.solve_joltages().ok_or_else(|| {
+            Error::Infeasible(format!("machine {} cannot reach its joltages", index + 1))
+        })?;
+        total_presses = total_presses.try_add(N::try_from_u64(presses as u64)?)?;
+    }
+
+    Ok(total_presses)
+}
+
+#[derive(Debug, Clone)]
+/// One machine. Light and counter `i` of `n` correspond to bit `n - 1 - i`,
+/// so the first light is the most significant bit.
+pub struct Problem {
+    /// Bitmask of the lights that must end up on.
+    pub lights: usize,
+    /// Bitmask of the lights (and counters) each button toggles.
+    pub buttons: Vec<usize>,
+    /// Target value of each counter.
+    pub joltages: Vec<usize>,
+}
+
+impl Problem {
+    /// Fewest button presses that turn on exactly `lights`. Pressing a
+    /// button twice undoes it, so this is a shortest path over light states.
+    pub fn solve_lights(&self) -> Option<usize> {
+        let mut states = 0;
+        let found = graph::bfs(
+            0,
+            |state| {
+                states += 1;
+                self.buttons
+                    .iter()
+                    .map(|button| state ^ button)
+                    .collect::<Vec<usize>>()
+            },
+            |state| *state == self.lights,
+        );
+        metrics::count("light_states", states);
+
+        found.map(|(_, presses)| presses)
+    }
+
+    /// Fewest button presses that raise every counter to its joltage, or
+    /// `None` if no combination of presses does.
+    pub fn solve_joltages(&self) -> Option<usize> {
+        let mut all_zeros = Vec::new();
+        (0..self.joltages.len()).for_each(|_| all_zeros.push(0.0));
+
+        // Build variables representing the number of times each button is
+        // pressed; our minimization objective is simply the sum of these
+        // variables.
+        let mut problem = ProblemVariables::new();
+        let variables = (0..self.buttons.len())
+            .map(|_| problem.add(variable().integer().min(0)))
+            .collect::<Vec<_>>();
+        let mut objective: Expression = 0.into();
+        for variable in &variables {
+            objective += variable
+        }
+
+        // Build constraints for each joltage target, where presses of a given
+        // button are included in the summation if the button would affect the
+        // current target.
+        let mut constraints: Vec<Constraint> = Vec::new();
+        for (i, joltage) in self.joltages.iter().enumerate() {
+            let mut summation: Expression = 0.into();
+            for (j, button) in self.buttons.iter().enumerate() {
+                let index = self.joltages.len() - 1 - i;
+                if button & (1 << index) != 0 {
+                    summation += variables[j]
+                }
+            }
+            constraints.push(constraint!(summation == (*joltage as f64)));
+        }
+
+        // Solve the LP.
+        metrics::count("lp_variables", variables.len() as u64);
+        let solution = metrics::time("lp_solve", || {
+            problem
+                .minimise(&objective)
+                .using(microlp)
+                .with_all(constraints)
+                .solve()
+        });
+        match solution {
+            Ok(solution) => Some(solution.eval(objective).round() as usize),
+            Err(_) => None,
+        }
+    }
+}
+
+pub fn parse_problems(input: &str) -> Result<Vec<Problem>, ParseError> {
+    let mut problems: Vec<Problem> = Vec::new();
+    for line in input.lines() {
+        let (lights_str, remaining) =
+            line.split_once(" ")
+                .context_at(input, line, "expected lights followed by buttons")?;
+        let lights_inner = lights_str
+            .strip_prefix('[')
+            .and_then(|lights| lights.strip_suffix(']'))
+            .context_at(input, lights_str, "expected lights in brackets")?;
+        // Lights and buttons are bitmasks, one bit per light.
+        if lights_inner.len() > usize::BITS as usize {
+            let message = format!("more than {} lights", usize::BITS);
+            return Err(ParseError::at(input, lights_str, message));
+        }
+        let mut lights = 0;
+        for (index, char) in lights_inner.char_indices() {
+            let bit = match char {
+                '#' => 1,
+                '.' => 0,
+                _ => {
+                    let token = &lights_inner[index..index + char.len_utf8()];
+                    return Err(ParseError::at(input, token, "expected '#' or '.'"));
+                }
+            };
+            lights = lights << 1 | bit;
+        }
+        let light_count = lights_inner.len();
+        let (buttons_str, joltages_str) = remaining.split_once(" {").context_at(
+            input,
+            remaining,
+            "expected buttons followed by joltages",
+        )?;
+        let buttons: Vec<usize> = buttons_str
+            .split(" ")
+            .map(|button| {
+                let positions = button
+                    .strip_prefix('(')
+                    .and_then(|button| button.strip_suffix(')'))
+                    .context_at(input, button, "expected button in parentheses")?;
+
+                let mut bitmask = 0;
+                for position_str in positions.split(',') {
+                    let position = position_str.parse::<usize>().context_at(
+                        input,
+                        position_str,
+                        "invalid position",
+                    )?;
+                    if position >= light_count {
+                        let message =
+                            format!("position is past the last of {} lights", light_count);
+                        return Err(ParseError::at(input, position_str, message));
+                    }
+                    bitmask |= 1 << (light_count - 1 - position);
+                }
+
+                Ok(bitmask)
+            })
+            .collect::<Result<Vec<usize>, ParseError>>()?;
+        let joltages: Vec<usize> = joltages_str
+            .strip_suffix('}')
+            .context_at(input, joltages_str, "expected joltages in braces")?
+            .split(',')
+            .map(|joltage| {
+                joltage
+                    .parse::<usize>()
+                    .context_at(input, joltage, "invalid joltage")
+            })
+            .collect::<Result<Vec<usize>, ParseError>>()?;
+        problems.push(Problem {
+            lights,
+            buttons,
+            joltages,
+        });
+    }
+
+    Ok(problems)
+}
+
+fn generate_problems(rng: &mut Rng, count: usize) -> String {
+    let mut problems = String::new();
+    for _ in 0..count {
+        let light_count = rng.index(10) + 1;
+        let button_count = rng.index(light_count + 3) + 1;
+        let mut lights = vec![false; light_count];
+        let mut joltages = vec![0; light_count];
+        let mut buttons = Vec::new();
+        for _ in 0..button_count {
+            let mut positions = (0..light_count)
+                .filter(|_| rng.chance(0.4))
+                .collect::<Vec<usize>>();
+            if positions.is_empty() {
+                positions.push(rng.index(light_count));
+            }
+
+            let presses = rng.index(10);
+            for position in &positions {
+                lights[*position] ^= presses % 2 == 1;
+                joltages[*position] += presses;
+            }
+            let positions = positions.iter().map(usize::to_string).collect::<Vec<_>>();
+            buttons.push(format!("({})", positions.join(",")));
+        }
+
+        let lights = lights
+            .iter()
+            .map(|on| if *on { '#' } else { '.' })
+            .collect::<String>();
+        let joltages = joltages.iter().map(usize::to_string).collect::<Vec<_>>();
+        problems.push_str(&format!(
+            "[{}] {} {{{}}}\n",
+            lights,
+            buttons.join(" "),
+            joltages.join(",")
+        ));
+    }
+
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracle::{self, shrink_integer, shrink_vec};
+
+    const EXAMPLE: &str = "\
+[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
+";
+
+    #[test]
+    fn example() {
+        let problems = parse_problems(EXAMPLE).unwrap();
+        assert_eq!(problem_1::<u64>(&problems).unwrap(), 7);
+        assert_eq!(problem_2::<u64>(&problems).unwrap(), 33);
+    }
+
+    #[test]
+    fn example_per_machine() {
+        let problems = parse_problems(EXAMPLE).unwrap();
+        let lights = problems
+            .iter()
+            .map(Problem::solve_lights)
+            .collect::<Vec<_>>();
+        let joltages = problems
+            .iter()
+            .map(Problem::solve_joltages)
+            .collect::<Vec<_>>();
+        assert_eq!(lights, [Some(2), Some(3), Some(2)]);
+        assert_eq!(joltages, [Some(10), Some(12), Some(11)]);
+    }
+
+    #[test]
+    fn rejects_too_many_lights() {
+        let lights = "#".repeat(usize::BITS as usize + 1);
+        let input = format!("[.#] (1) {{1,1}}\n[{}] (0) {{1}}\n", lights);
+        let error = parse_problems(&input).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.message, format!("more than {} lights", usize::BITS));
+
+        let lights = "#".repeat(usize::BITS as usize);
+        let input = format!("[{}] (0) {{1}}\n", lights);
+        assert!(parse_problems(&input).is_ok());
+    }
+
+    /// Tries every number of presses for each button, up to the smallest
+    /// joltage it adds to.
+    fn solve_joltages_oracle(problem: &Problem) -> Option<usize> {
+        let counters = problem.joltages.len();
+        let affects = |button: usize, counter: usize| button & (1 << (counters - 1 - counter)) != 0;
+        let limits = problem
+            .buttons
+            .iter()
+            .map(|button| {
+                (0..counters)
+                    .filter(|counter| affects(*button, *counter))
+                    .map(|counter| problem.joltages[counter])
+                    .min()
+                    .unwrap_or(0)
+            })
+            .collect::<Vec<usize>>();
+
+        let mut best = None;
+        let mut presses = vec![0; problem.buttons.len()];
+        loop {
+            let joltages = (0..counters)
+                .map(|counter| {
+                    (0..presses.len())
+                        .filter(|button| affects(problem.buttons[*button], counter))
+                        .map(|button| presses[button])
+                        .sum::<usize>()
+                })
+                .collect::<Vec<usize>>();
+            if joltages == problem.joltages {
+                let total = presses.iter().sum::<usize>();
+                best = Some(best.map_or(total, |best: usize| best.min(total)));
+            }
+
+            // Count up through every combination of presses.
+            let Some(button) = (0..presses.len()).find(|button| presses[*button] < limits[*button])
+            else {
+                return best;
+            };
+            presses[button] += 1;
+            presses[..button].fill(0);
+        }
+    }
+
+    fn shrink_problem(problem: &Problem) -> Vec<Problem> {
+        let clear_bits = |button: &usize| {
+            (0..usize::BITS)
+                .filter(|bit| button & (1 << bit) != 0)
+                .map(|bit| button & !(1 << bit))
+                .collect()
+        };
+        let mut candidates = shrink_vec(&problem.buttons, clear_bits)
+            .into_iter()
+            .map(|buttons| Problem {
+                buttons,
+                ..problem.clone()
+            })
+            .collect::<Vec<Problem>>();
+        for (counter, joltage) in problem.joltages.iter().enumerate() {
+            for smaller in shrink_integer(*joltage as i64, 0) {
+                let mut joltages = problem.joltages.clone();
+                joltages[counter] = smaller as usize;
+                candidates.push(Problem {
+                    joltages,
+                    ..problem.clone()
+                });
+            }
+        }
+
+        candidates
+    }
+
+    #[test]
+    fn solve_joltages_matches_oracle() {
+        oracle::assert_agrees(
+            |rng| {
+                let counters = rng.index(5) + 1;
+                Problem {
+                    lights: 0,
+                    buttons: (0..rng.index(5) + 1)
+                        .map(|_| rng.index((1 << counters) - 1) + 1)
+                        .collect(),
+                    joltages: (0..counters).map(|_| rng.index(9)).collect(),
+                }
+            },
+            shrink_problem,
+            Problem::solve_joltages,
+            solve_joltages_oracle,
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use crate::{
+    Solution, budget,
+    error::{self, Error},
+    generate::Rng,
+    graph::{Graph, NodeId},
+    num::{self, Integer},
+    parse::{ParseContext, ParseError},
+};
+use std::fmt::Display;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Graph<String>;
+    const DAY: u8 = 11;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_graph(input)
+    }
+
+    /// A DAG of `size` nodes besides the named ones. A chain through every
+    /// node guarantees paths from `you` and through `dac` and `fft`.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_graph(rng, size)
+    }
+
+    fn part_1(graph: &Self::Input) -> error::Result<impl Display> {
+        let unreachable = || Error::Infeasible(format!("{} does not lead to {}", YOU, OUT));
+        num::in_mode(
+            || problem_1::<u64>(graph)?.ok_or_else(unreachable),
+            || problem_1::<u128>(graph)?.ok_or_else(unreachable),
+        )
+    }
+
+    fn part_2(graph: &Self::Input) -> error::Result<impl Display> {
+        let unreachable = || Error::Infeasible(format!("{} does not lead to {}", SVR, OUT));
+        num::in_mode(
+            || problem_2::<u64>(graph)?.ok_or_else(unreachable),
+            || problem_2::<u128>(graph)?.ok_or_else(unreachable),
+        )
+    }
+}
+
+const YOU: &str = "you";
+const OUT: &str = "out";
+const SVR: &str = "svr";
+const DAC: &str = "dac";
+const FFT: &str = "fft";
+
+pub fn problem_1<N: Integer>(graph: &Graph<String>) -> error::Result<Option<N>> {
+    let Some(you) = graph.id(&YOU.to_string()) else {
+        return Ok(None);
+    };
+    let mut paths_to = vec![N::ZERO; graph.len()];
+    paths_to[you] = N::ONE;
+
+    // Iterate over the sources in topological order, accumulating our target
+    // metric at each node that has been fully-visited.
+    for source in sorted_from(graph, you)? {
+        budget::step(1)?;
+        for sink in graph.sinks(source) {
+            paths_to[*sink] = paths_to[*sink].try_add(paths_to[source])?;
+        }
+    }
+
+    Ok(graph.id(&OUT.to_string()).map(|out| paths_to[out]))
+}
+
+pub fn problem_2<N: Integer>(graph: &Graph<String>) -> error::Result<Option<N>> {
+    let Some(svr) = graph.id(&SVR.to_string()) else {
+        return Ok(None);
+    };
+    let (dac, fft) = (graph.id(&DAC.to_string()), graph.id(&FFT.to_string()));
+    let mut paths_to = vec![[N::ZERO; 4]; graph.len()];
+    paths_to[svr] = [N::ONE, N::ZERO, N::ZERO, N::ZERO];
+
+    // Iterate over the sources in topological order, accumulating our target
+    // metric at each node that has been fully-visited.
+    for source in sorted_from(graph, svr)? {
+        budget::step(1)?;
+        // Unlike the first problem, we need to segment our metric by DAC/FFT
+        // visited status. We use special logic to add these segmented metrics
+        // when visiting each node.
+        let [mut paths_none, mut paths_dac, mut paths_fft, mut paths_both] = paths_to[source];
+        if Some(source) == dac {
+            paths_both = paths_both.try_add(paths_fft)?;
+            paths_dac = paths_dac.try_add(paths_none)?;
+            paths_fft = N::ZERO;
+            paths_none = N::ZERO;
+        } else if Some(source) == fft {
+            paths_both = paths_both.try_add(paths_dac)?;
+            paths_fft = paths_fft.try_add(paths_none)?;
+            paths_dac = N::ZERO;
+            paths_none = N::ZERO;
+        }
+
+        let paths_after_visit = [paths_none, paths_dac, paths_fft, paths_both];
+        for sink in graph.sinks(source) {
+            for (paths, after_visit) in paths_to[*sink].iter_mut().zip(paths_after_visit) {
+                *paths = paths.try_add(after_visit)?;
+            }
+        }
+    }
+
+    Ok(graph.id(&OUT.to_string()).map(|out| paths_to[out][3]))
+}
+
+/// The nodes reachable from `source`, each before its sinks. Paths through a
+/// cycle could be followed forever, so one makes the input infeasible.
+fn sorted_from(graph: &Graph<String>, source: NodeId) -> error::Result<Vec<NodeId>> {
+    let reachable = graph.reachable_from(source);
+    graph
+        .topological_sort(|node| reachable[node])
+        .map_err(|cycle| Error::Infeasible(format!("cycle: {}", cycle.describe(graph))))
+}
+
+pub fn parse_graph(input: &str) -> Result<Graph<String>, ParseError> {
+    let mut graph = Graph::new();
+    for line in input.lines() {
+        let (source, sinks) =
+            line.split_once(": ")
+                .context_at(input, line, "expected `source: sink ...`")?;
+        graph.intern(source.to_string());
+        for sink in sinks.split(" ") {
+            graph.add_edge(source.to_string(), sink.to_string());
+        }
+    }
+
+    Ok(graph)
+}
+
+fn generate_graph(rng: &mut Rng, count: usize) -> String {
+    let mut middle = vec![YOU.to_string(), DAC.to_string(), FFT.to_string()];
+    while middle.len() < count + 3 {
+        let name = (0..3)
+            .map(|_| (b'a' + rng.index(26) as u8) as char)
+            .collect::<String>();
+        if ![SVR, OUT].contains(&name.as_str()) && !middle.contains(&name) {
+            middle.push(name);
+        }
+    }
+    rng.shuffle(&mut middle);
+
+    // Nodes in topological order; edges only point forward.
+    let mut nodes = vec![SVR.to_string()];
+    nodes.extend(middle);
+    nodes.push(OUT.to_string());
+    let mut lines = Vec::new();
+    for index in 0..nodes.len() - 1 {
+        let mut sinks = vec![index + 1];
+        for _ in 0..rng.index(3) {
+            let sink = index + 1 + rng.index(nodes.len() - index - 1);
+            if !sinks.contains(&sink) {
+                sinks.push(sink);
+            }
+        }
+        let sinks = sinks
+            .iter()
+            .map(|sink| nodes[*sink].as_str())
+            .collect::<Vec<_>>();
+        lines.push(format!("{}: {}\n", nodes[index], sinks.join(" ")));
+    }
+    rng.shuffle(&mut lines);
+
+    lines.concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_1: &str = "\
+aaa: you hhh
+you: bbb ccc
+bbb: ddd eee
+ccc: ddd eee fff
+ddd: ggg
+eee: out
+fff: out
+ggg: out
+hhh: ccc fff iii
+iii: out
+";
+
+    const EXAMPLE_2: &str = "\
+svr: aaa bbb
+aaa: fft
+fft: ccc
+bbb: tty
+tty: ccc
+ccc: ddd eee
+ddd: hub
+hub: fff
+eee: dac
+dac: fff
+fff: ggg hhh
+ggg: out
+hhh: out
+";
+
+    #[test]
+    fn example() {
+        let graph_1 = Day11::parse(EXAMPLE_1).unwrap();
+        let graph_2 = Day11::parse(EXAMPLE_2).unwrap();
+        assert_eq!(problem_1::<u64>(&graph_1).unwrap(), Some(5));
+        assert_eq!(problem_2::<u64>(&graph_2).unwrap(), Some(2));
+    }
+
+    #[test]
+    fn reports_cycles() {
+        let graph = Day11::parse("you: aaa\naaa: bbb\nbbb: aaa out\n").unwrap();
+        let error = problem_1::<u64>(&graph).unwrap_err();
+        assert_eq!(error.to_string(), "no solution: cycle: aaa -> bbb -> aaa");
+        assert_eq!(problem_2::<u64>(&graph).unwrap(), None);
+    }
+}
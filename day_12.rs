@@ -0,0 +1,447 @@
+use crate::{
+    Solution, budget, error,
+    generate::Rng,
+    grid, metrics,
+    parse::{ParseContext, ParseError},
+    visualize,
+};
+use std::{collections::HashMap, fmt::Display};
+
+// Note: This problem is a troll.
+// My method actually works but only because the input is constructed in a way
+// where solvable grids are trivial and all other grids can be bailed out on
+// after a certain number of iterations. Happy holidays!
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = (Vec<Piece>, Vec<Grid>);
+    const DAY: u8 = 12;
+    const PARTS: u8 = 1;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_grid(input)
+    }
+
+    /// Six random 3x3 pieces and `size` regions needing at most two of each.
+    /// Like real inputs, a region either has a 3x3 block for every piece it
+    /// needs or is a single row that no piece fits in, so the search settles
+    /// each region quickly.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_pieces_and_regions(rng, size)
+    }
+
+    fn part_1((pieces, grids): &Self::Input) -> error::Result<impl Display> {
+        problem_1(pieces, &mut grids.clone())
+    }
+}
+
+/// Number of grids that can be filled. Each grid gets the whole step limit of
+/// the armed budget (see `budget::fresh`), and grids that use it up are
+/// counted as unsolvable, which makes the total a guess. Without a step limit,
+/// ruling out a grid can take very long. Fails with `BudgetExceeded` only when
+/// the budget runs out of time or is cancelled. The outcome of each grid is
+/// recorded as a `visualize` frame.
+pub fn problem_1(pieces: &[Piece], grids: &mut [Grid]) -> error::Result<usize> {
+    let mut solvable = 0;
+    let count = grids.len();
+    for (index, grid) in grids.iter_mut().enumerate() {
+        let result = metrics::time("solve_grid", || budget::fresh(|| solve_grid(grid, pieces)));
+        metrics::count("placements", grid.trials as u64);
+        let fits = result?;
+        match fits {
+            Some(true) => solvable += 1,
+            Some(false) => {}
+            None => {
+                metrics::count("grids_given_up", 1);
+                crate::mark_unknown();
+            }
+        }
+        visualize::frame(
+            || {
+                let outcome = match fits {
+                    Some(true) => "fits",
+                    Some(false) => "does not fit",
+                    None => "gave up",
+                };
+                format!("region {} of {}: {}", index + 1, count, outcome)
+            },
+            || grid.render(pieces),
+        );
+    }
+
+    Ok(solvable)
+}
+
+/// Whether the remaining pieces of `grid` can all be placed, by backtracking.
+/// Each attempt to place a piece is one step of the armed budget, and each
+/// placement is recorded as a `visualize` frame.
+pub fn solve_grid(grid: &mut Grid, pieces: &[Piece]) -> error::Result<bool> {
+    if grid.remaining.iter().all(|(_, count)| *count == 0) {
+        return Ok(true);
+    }
+
+    // Backtracking packer algorithm.
+    for index in 0..pieces.len() {
+        for orientation in 0..pieces[index].grids.len() {
+            for row in 0..grid.grid.rows() {
+                for column in 0..grid.grid.columns() {
+                    budget::step(1)?;
+                    if grid.place(pieces, index, orientation, row, column) {
+                        visualize::frame(
+                            || {
+                                format!(
+                                    "placed {} at {},{} after {} trials",
+                                    (b'A' + (index % 26) as u8) as char,
+                                    row,
+                                    column,
+                                    grid.trials
+                                )
+                            },
+                            || grid.render(pieces),
+                        );
+                        let result = solve_grid(grid, pieces);
+                        match result {
+                            Ok(true) => return Ok(true),
+                            Ok(false) => {
+                                grid.pop_last(pieces);
+                                continue;
+                            }
+                            Err(error) => return Err(error),
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+/// A present shape in every distinct orientation.
+#[derive(Debug)]
+pub struct Piece {
+    grids: Vec<grid::Grid<char>>,
+}
+
+/// A region under the tree, with the pieces still to place in it.
+#[derive(Debug, Clone)]
+pub struct Grid {
+    grid: grid::Grid<char>,
+    trials: usize,
+    placed: Vec<(usize, usize, usize, usize)>,
+    remaining: HashMap<usize, usize>,
+}
+
+impl Grid {
+    fn place(
+        &mut self,
+        pieces: &[Piece],
+        index: usize,
+        orientation: usize,
+        row: usize,
+        column: usize,
+    ) -> bool {
+        self.trials += 1;
+        if *self.remaining.get(&index).unwrap_or(&0) == 0 {
+            return false;
+        }
+
+        // Check if the piece can be placed.
+        let piece = &pieces[index];
+        let piece_grid = &piece.grids[orientation];
+        for (row_offset, column_offset) in piece_grid.positions() {
+            if piece_grid[(row_offset, column_offset)] == '.' {
+                continue;
+            }
+
+            // Non-empty piece cell out of bounds or overlapping another piece.
+            let target = self
+                .grid
+                .get((row + row_offset) as i64, (column + column_offset) as i64);
+            if target != Some(&'.') {
+                return false;
+            }
+        }
+
+        // Place the piece.
+        for (row_offset, column_offset) in piece_grid.positions() {
+            let cell = piece_grid[(row_offset, column_offset)];
+            if cell != '.' {
+                self.grid[(row + row_offset, column + column_offset)] = cell;
+            }
+        }
+
+        // Update internal grid state for piece tracking.
+        self.placed.push((index, orientation, row, column));
+        self.remaining.entry(index).and_modify(|count| *count -= 1);
+        true
+    }
+
+    /// The region with each placed piece drawn in its own letter: `A` for
+    /// the first piece, `B` for the second and so on.
+    fn render(&self, pieces: &[Piece]) -> grid::Grid<char> {
+        let mut cells = grid::Grid::new(self.grid.rows(), self.grid.columns(), '.');
+        for (index, orientation, row, column) in &self.placed {
+            let piece_grid = &pieces[*index].grids[*orientation];
+            for (row_offset, column_offset) in piece_grid.positions() {
+                if piece_grid[(row_offset, column_offset)] != '.' {
+                    cells[(row + row_offset, column + column_offset)] =
+                        (b'A' + (*index % 26) as u8) as char;
+                }
+            }
+        }
+
+        cells
+    }
+
+    fn pop_last(&mut self, pieces: &[Piece]) {
+        let Some((index, orientation, row, column)) = self.placed.pop() else {
+            return;
+        };
+        let piece = &pieces[index];
+        let piece_grid = &piece.grids[orientation];
+        for (row_offset, column_offset) in piece_grid.positions() {
+            // Cell was occupied by the piece; clear it (cells out of bounds
+            // are ignored).
+            if piece_grid[(row_offset, column_offset)] != '.' {
+                self.grid.set(
+                    (row + row_offset) as i64,
+                    (column + column_offset) as i64,
+                    '.',
+                );
+            }
+        }
+
+        // Update internal grid state for piece tracking.
+        self.remaining.entry(index).and_modify(|count| *count += 1);
+    }
+}
+
+pub fn parse_grid(input: &str) -> Result<(Vec<Piece>, Vec<Grid>), ParseError> {
+    let blocks = crate::split_blocks(input);
+    let (grids, pieces) = blocks
+        .split_last()
+        .filter(|(_, pieces)| !pieces.is_empty())
+        .ok_or_else(|| ParseError::at_end(input, "expected pieces followed by regions"))?;
+    let pieces = pieces
+        .iter()
+        .map(|piece| {
+            // Skip the index line heading each piece.
+            let (_, shape) =
+                piece
+                    .split_once('\n')
+                    .context_at(input, piece, "piece has no shape")?;
+            let grid = grid::Grid::parse(shape).map_err(|error| error.within(input, shape))?;
+            Ok(Piece {
+                grids: get_orientations(&grid),
+            })
+        })
+        .collect::<Result<Vec<Piece>, ParseError>>()?;
+    let grids = grids
+        .lines()
+        .map(|line| {
+            let (dimensions, counts) =
+                line.split_once(": ")
+                    .context_at(input, line, "expected `rowsxcolumns: counts`")?;
+            let (rows, columns) = dimensions.split_once("x").context_at(
+                input,
+                dimensions,
+                "expected `rowsxcolumns`",
+            )?;
+            let rows = rows
+                .parse::<usize>()
+                .context_at(input, rows, "invalid rows")?;
+            let columns = columns
+                .parse::<usize>()
+                .context_at(input, columns, "invalid columns")?;
+            let counts = counts
+                .split(" ")
+                .map(|count| {
+                    count
+                        .parse::<usize>()
+                        .context_at(input, count, "invalid count")
+                })
+                .collect::<Result<Vec<usize>, ParseError>>()?;
+            Ok(Grid {
+                grid: grid::Grid::new(rows, columns, '.'),
+                trials: 0,
+                placed: Vec::new(),
+                remaining: counts
+                    .iter()
+                    .enumerate()
+                    .map(|(piece_index, count)| (piece_index, *count))
+                    .collect(),
+            })
+        })
+        .collect::<Result<Vec<Grid>, ParseError>>()?;
+    Ok((pieces, grids))
+}
+
+pub fn get_orientations(grid: &grid::Grid<char>) -> Vec<grid::Grid<char>> {
+    let mut orientations = Vec::new();
+    let mut new_grid = grid.clone();
+    for _ in 0..4 {
+        orientations.push(new_grid.clone());
+        new_grid = new_grid.rotate_left();
+    }
+
+    orientations
+}
+
+fn generate_pieces_and_regions(rng: &mut Rng, regions: usize) -> String {
+    let piece_count = 6;
+    let mut blocks = Vec::new();
+    for index in 0..piece_count {
+        let mut piece = grid::Grid::new(3, 3, '.');
+        for position in piece.positions() {
+            if rng.chance(0.6) {
+                piece[position] = '#';
+            }
+        }
+        // The centre and two of its neighbours make every orientation span
+        // at least two rows and two columns.
+        piece[(1, 1)] = '#';
+        piece[(0, 1)] = '#';
+        piece[(1, 0)] = '#';
+        blocks.push(format!("{}:\n{}", index, piece));
+    }
+
+    let mut lines = String::new();
+    for _ in 0..regions {
+        let counts = (0..piece_count).map(|_| rng.index(3)).collect::<Vec<_>>();
+        let needed = counts.iter().sum::<usize>().max(1);
+        let (rows, columns) = if rng.chance(0.5) {
+            let block_columns = rng.index(needed) + 1;
+            let block_rows = needed.div_ceil(block_columns);
+            (
+                3 * block_rows + rng.index(3),
+                3 * block_columns + rng.index(3),
+            )
+        } else {
+            (1, rng.index(3 * needed) + 3)
+        };
+        let counts = counts.iter().map(usize::to_string).collect::<Vec<_>>();
+        lines.push_str(&format!("{}x{}: {}\n", rows, columns, counts.join(" ")));
+    }
+    blocks.push(lines);
+
+    blocks.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example's third region does not fit, which the search only gives
+    // up on once it runs out of steps.
+    const EXAMPLE: &str = "\
+0:
+###
+##.
+##.
+
+1:
+###
+##.
+.##
+
+2:
+.##
+###
+##.
+
+3:
+##.
+###
+##.
+
+4:
+###
+#..
+###
+
+5:
+###
+.#.
+###
+
+4x4: 0 0 0 0 2 0
+12x5: 1 0 1 0 2 2
+12x5: 1 0 1 0 3 2
+";
+
+    fn solve_example(budget: budget::Budget) -> error::Result<(usize, Option<metrics::Metric>)> {
+        let (pieces, mut grids) = Day12::parse(EXAMPLE).unwrap();
+        assert_eq!(pieces.len(), 6);
+        let _armed = budget::start(&budget);
+        metrics::take();
+        let solvable = problem_1(&pieces, &mut grids)?;
+        Ok((solvable, metrics::take().get("grids_given_up")))
+    }
+
+    #[test]
+    fn example() {
+        let budget = budget::Budget {
+            steps: Some(300_000),
+            ..budget::Budget::default()
+        };
+        assert_eq!(
+            solve_example(budget).unwrap(),
+            (2, Some(metrics::Metric::Counter(1)))
+        );
+    }
+
+    #[test]
+    fn step_limit_gives_up_on_every_grid() {
+        let budget = budget::Budget {
+            steps: Some(5),
+            ..budget::Budget::default()
+        };
+        assert_eq!(
+            solve_example(budget).unwrap(),
+            (0, Some(metrics::Metric::Counter(3)))
+        );
+    }
+
+    #[test]
+    fn cancellation_gives_up_on_the_answer() {
+        let budget = budget::Budget::default();
+        budget.cancel.cancel();
+        assert!(matches!(
+            solve_example(budget),
+            Err(error::Error::BudgetExceeded(_))
+        ));
+    }
+
+    #[test]
+    fn only_part_1_exists() {
+        let input = Day12::parse(EXAMPLE).unwrap();
+        for part in [0, 2, 7] {
+            assert!(matches!(
+                crate::solve_part::<Day12>(&input, part),
+                Err(error::Error::Usage(_))
+            ));
+        }
+        assert!(matches!(
+            Day12::part_2(&input).map(|answer| answer.to_string()),
+            Err(error::Error::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn records_placements() {
+        let (pieces, mut grids) = Day12::parse(EXAMPLE).unwrap();
+        let (result, recording) = visualize::record(1000, || problem_1(&pieces, &mut grids[..1]));
+        assert_eq!(result.unwrap(), 1);
+        let captions = recording
+            .frames
+            .iter()
+            .map(|frame| frame.caption.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(captions.len(), 3);
+        assert!(captions[0].starts_with("placed E at 0,0"), "{:?}", captions);
+        assert_eq!(captions[2], "region 1 of 1: fits");
+        let cells = recording.frames[2].cells.to_string();
+        assert_eq!(cells.matches('E').count(), 14, "{}", cells);
+    }
+}
@@ -0,0 +1,257 @@
+use crate::{Solution, error, generate::Rng, parse::ParseError, range::Range};
+use std::{collections::HashSet, fmt::Display};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Range>;
+    const DAY: u8 = 2;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_ranges(input)
+    }
+
+    /// `size` (at least one) disjoint ranges with up to ten-digit bounds.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_ranges(rng, size.max(1))
+    }
+
+    fn part_1(ranges: &Self::Input) -> error::Result<impl Display> {
+        Ok(problem_1(ranges))
+    }
+
+    fn part_2(ranges: &Self::Input) -> error::Result<impl Display> {
+        Ok(problem_2(ranges))
+    }
+}
+
+pub fn problem_1(ranges: &[Range]) -> i64 {
+    ranges.iter().map(sum_invalid).sum()
+}
+
+pub fn sum_invalid(range: &Range) -> i64 {
+    let mut sum_invalid_total = 0;
+    let digits_start = count_digits(range.start);
+    let digits_end = count_digits(range.end);
+
+    let mut current_start = range.start;
+    let mut current_end: i64;
+    // Iterate over each digit count in the range.
+    for digit_count in digits_start..=digits_end {
+        // This iteration either ends at the overall range end or at `99...9`
+        // in the current digit count.
+        if digit_count == digits_end {
+            current_end = range.end
+        } else {
+            current_end = 10_i64.pow(digit_count as u32) - 1;
+        }
+
+        // No odd-digit numbers can be invalid.
+        if digit_count % 2 != 0 {
+            current_start = current_end + 1;
+            continue;
+        }
+
+        // Sum invalid numbers in range (where the first half of number is
+        // duplicated).
+        let current_start_first_half = current_start / 10_i64.pow(digit_count as u32 / 2);
+        let current_start_last_half = current_start % 10_i64.pow(digit_count as u32 / 2);
+        let current_end_first_half = current_end / 10_i64.pow(digit_count as u32 / 2);
+        let current_end_last_half = current_end % 10_i64.pow(digit_count as u32 / 2);
+        for number in current_start_first_half..=current_end_first_half {
+            // Handle cases where duplicating the first half is not in the
+            // range as implied by the last half.
+            if number == current_start_first_half
+                && number == current_end_first_half
+                && (number < current_start_last_half || number > current_end_last_half)
+            {
+                // Case 1: Start and end have same first half and number is not
+                // in the range implied by the last halves.
+                continue;
+            }
+            if number == current_start_first_half && number < current_start_last_half {
+                // Case 2: Number has same first half as start but less than
+                // its last half.
+                continue;
+            }
+            if number == current_end_first_half && number > current_end_last_half {
+                // Case 3: Number has same first half as end but greater than
+                // its last half.
+                continue;
+            }
+            sum_invalid_total += number * 10_i64.pow(digit_count as u32 / 2) + number;
+        }
+
+        current_start = current_end + 1;
+    }
+
+    sum_invalid_total
+}
+
+fn count_digits(number: i64) -> i64 {
+    number.to_string().len() as i64
+}
+
+pub fn problem_2(ranges: &[Range]) -> i64 {
+    let mut seen_numbers = HashSet::new();
+    let mut sum_invalid_total = 0;
+    for range in ranges {
+        let digits_start = count_digits(range.start);
+        let digits_end = count_digits(range.end);
+
+        let mut current_start = range.start;
+        let mut current_end: i64;
+        // Iterate over each digit count in the range.
+        for digit_count in digits_start..=digits_end {
+            // This iteration either ends at the overall range end or at `99...9`
+            // in the current digit count.
+            if digit_count == digits_end {
+                current_end = range.end
+            } else {
+                current_end = 10_i64.pow(digit_count as u32) - 1;
+            }
+
+            // Iterate over each possible split size.
+            for split_size in 1..=digit_count / 2 {
+                if digit_count % split_size != 0 {
+                    continue;
+                }
+
+                let current_start_first_part =
+                    current_start / 10_i64.pow(digit_count as u32 - split_size as u32);
+                let current_end_first_part =
+                    current_end / 10_i64.pow(digit_count as u32 - split_size as u32);
+
+                // Iterate over each value to duplicate.
+                for value in current_start_first_part..=current_end_first_part {
+                    // Replicate `value` by `digit_count / split_size` times.
+                    let replicated_value = value
+                        .to_string()
+                        .repeat(digit_count as usize / split_size as usize);
+                    let replicated_value_i64 = replicated_value.parse::<i64>().unwrap();
+
+                    // Brute-force check for being in range.
+                    if replicated_value_i64 < current_start || replicated_value_i64 > current_end {
+                        continue;
+                    }
+
+                    // Skip if we've already seen this number (can happen with
+                    // overlapping splits as in 222222).
+                    if seen_numbers.contains(&replicated_value_i64) {
+                        continue;
+                    }
+
+                    seen_numbers.insert(replicated_value_i64);
+                    sum_invalid_total += replicated_value_i64;
+                }
+            }
+
+            current_start = current_end + 1;
+        }
+    }
+
+    sum_invalid_total
+}
+
+pub fn parse_ranges(input: &str) -> Result<Vec<Range>, ParseError> {
+    let mut ranges = Vec::new();
+    for line in input.lines() {
+        for range_str in line.split(',') {
+            let range = range_str
+                .parse::<Range>()
+                .map_err(|error| error.within(input, range_str))?;
+            ranges.push(range);
+        }
+    }
+
+    Ok(ranges)
+}
+
+fn generate_ranges(rng: &mut Rng, count: usize) -> String {
+    let mut ranges: Vec<Range> = Vec::new();
+    while ranges.len() < count {
+        let digits = rng.range(1..=10) as u32;
+        let start = rng.range(1..=10_i64.pow(digits) - 1);
+        let end = start + rng.range(0..=10_i64.pow(digits.min(4)));
+        let range = Range::new(start, end).expect("end is not before start");
+        if ranges
+            .iter()
+            .all(|other| other.intersection(&range).is_none())
+        {
+            ranges.push(range);
+        }
+    }
+
+    let ranges = ranges.iter().map(Range::to_string).collect::<Vec<_>>();
+    format!("{}\n", ranges.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracle::{self, shrink_integer};
+
+    const EXAMPLE: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,\
+1698522-1698528,446443-446449,38593856-38593862,565653-565659,\
+824824821-824824827,2121212118-2121212124
+";
+
+    #[test]
+    fn example() {
+        let ranges = Day2::parse(EXAMPLE).unwrap();
+        assert_eq!(problem_1(&ranges), 1227775554);
+        assert_eq!(problem_2(&ranges), 4174379265);
+    }
+
+    #[test]
+    fn sum_invalid_spans_digit_counts() {
+        // 99 and 1010 are the only doubled numbers in range.
+        assert_eq!(sum_invalid(&Range::new(95, 1012).unwrap()), 99 + 1010);
+        assert_eq!(sum_invalid(&Range::new(100, 999).unwrap()), 0);
+        // The start's halves match but the end has a larger first half.
+        assert_eq!(sum_invalid(&Range::new(5656, 5700).unwrap()), 5656);
+    }
+
+    /// Checks every number in the range for being some digits repeated twice.
+    fn sum_invalid_oracle(range: &Range) -> i64 {
+        (range.start..=range.end)
+            .filter(|number| {
+                let digits = number.to_string();
+                let (first, second) = digits.split_at(digits.len() / 2);
+                first == second
+            })
+            .sum()
+    }
+
+    fn shrink_range(range: &Range) -> Vec<Range> {
+        let length = range.end - range.start;
+        let from_end = shrink_integer(range.end, range.start)
+            .into_iter()
+            .map(|end| (range.start, end));
+        let from_start = shrink_integer(range.start, range.end)
+            .into_iter()
+            .map(|start| (start, range.end));
+        let lower = shrink_integer(range.start, 1)
+            .into_iter()
+            .map(|start| (start, start + length));
+        from_end
+            .chain(from_start)
+            .chain(lower)
+            .filter_map(|(start, end)| Range::new(start, end))
+            .collect()
+    }
+
+    #[test]
+    fn sum_invalid_matches_oracle() {
+        oracle::assert_agrees(
+            |rng| {
+                let digits = rng.range(1..=8) as u32;
+                let start = rng.range(1..=10_i64.pow(digits));
+                Range::new(start, start + rng.range(0..=2000)).unwrap()
+            },
+            shrink_range,
+            sum_invalid,
+            sum_invalid_oracle,
+        );
+    }
+}
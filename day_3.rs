@@ -0,0 +1,172 @@
+use crate::{
+    Solution, error,
+    generate::Rng,
+    num::{self, Integer},
+    parse::{ParseContext, ParseError},
+};
+use std::cmp::max;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Vec<i64>>;
+    const DAY: u8 = 3;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_banks(input)
+    }
+
+    /// `size` banks of `max(size, 12)` digits each.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_banks(rng, size, size.max(12))
+    }
+
+    fn part_1(banks: &Self::Input) -> error::Result<impl Display> {
+        num::in_mode(|| problem_1::<i64>(banks), || problem_1::<i128>(banks))
+    }
+
+    fn part_2(banks: &Self::Input) -> error::Result<impl Display> {
+        num::in_mode(|| problem_2::<i64>(banks), || problem_2::<i128>(banks))
+    }
+}
+
+pub fn problem_1<N: Integer>(banks: &Vec<Vec<i64>>) -> error::Result<N> {
+    let mut total_joltage = N::ZERO;
+    for bank in banks {
+        let mut largest_seen_two_digits = -1;
+        let mut largest_seen_digit = -1;
+        for digit in bank {
+            let largest_two_digits_here = largest_seen_digit * 10 + digit;
+            if *digit > largest_seen_digit {
+                largest_seen_digit = *digit;
+            }
+            if largest_two_digits_here > largest_seen_two_digits {
+                largest_seen_two_digits = largest_two_digits_here;
+            }
+        }
+
+        total_joltage = total_joltage.try_add(N::try_from_i64(largest_seen_two_digits)?)?;
+    }
+
+    Ok(total_joltage)
+}
+
+/// `T[i, k]`: After considering `i` elements of the bank and accepting `k`
+/// proposals (out of some maximum `M`), this is the highest value we were able
+/// to construct:
+///     - `T[0, k] = 0`
+///     - `T[i, 0] = 0`
+///     - `T[i + 1, k] = max(T[i, k], T[i,  k - 1] * 10 + B[i])`
+///
+/// Twelve digits always fit in an `i64`; only the total needs `N`.
+pub fn problem_2<N: Integer>(banks: &Vec<Vec<i64>>) -> error::Result<N> {
+    let max_proposals = 12;
+    let mut total_joltage = N::ZERO;
+    for bank in banks {
+        let mut t: HashMap<(usize, u32), i64> = HashMap::new();
+        for k in 0..=max_proposals {
+            for (i, digit) in bank.iter().enumerate() {
+                if k == 0 {
+                    t.insert((i + 1, k), 0);
+                } else {
+                    let skip_position_value = *t.get(&(i, k)).unwrap_or(&0);
+                    let take_position_value = t.get(&(i, k - 1)).unwrap_or(&0) * 10 + *digit;
+                    t.insert((i + 1, k), max(skip_position_value, take_position_value));
+                }
+            }
+        }
+
+        let joltage = *t.get(&(bank.len(), 12)).unwrap_or(&0);
+        total_joltage = total_joltage.try_add(N::try_from_i64(joltage)?)?;
+    }
+
+    Ok(total_joltage)
+}
+
+pub fn parse_banks(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    let mut banks = Vec::new();
+    for line in input.lines() {
+        let mut bank = Vec::new();
+        for (index, char) in line.char_indices() {
+            let token = &line[index..index + char.len_utf8()];
+            let digit = char
+                .to_digit(10)
+                .context_at(input, token, "expected a digit")?;
+            bank.push(digit as i64);
+        }
+        banks.push(bank);
+    }
+
+    Ok(banks)
+}
+
+fn generate_banks(rng: &mut Rng, count: usize, length: usize) -> String {
+    let mut banks = String::new();
+    for _ in 0..count {
+        for _ in 0..length {
+            banks.push_str(&rng.range(1..=9).to_string());
+        }
+        banks.push('\n');
+    }
+
+    banks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracle::{self, shrink_integer, shrink_vec};
+
+    const EXAMPLE: &str = "\
+987654321111111
+811111111111119
+234234234234278
+818181911112111
+";
+
+    #[test]
+    fn example() {
+        let banks = Day3::parse(EXAMPLE).unwrap();
+        assert_eq!(problem_1::<i64>(&banks).unwrap(), 357);
+        assert_eq!(problem_2::<i64>(&banks).unwrap(), 3121910778619);
+    }
+
+    /// Tries every choice of 12 batteries (or all of them in shorter banks).
+    fn problem_2_oracle(banks: &Vec<Vec<i64>>) -> i64 {
+        let mut total_joltage = 0;
+        for bank in banks {
+            let chosen = bank.len().min(12) as u32;
+            total_joltage += (0..1_u32 << bank.len())
+                .filter(|mask| mask.count_ones() == chosen)
+                .map(|mask| {
+                    bank.iter()
+                        .enumerate()
+                        .filter(|(index, _)| mask & (1 << index) != 0)
+                        .fold(0, |joltage, (_, digit)| joltage * 10 + digit)
+                })
+                .max()
+                .unwrap_or(0);
+        }
+
+        total_joltage
+    }
+
+    #[test]
+    fn problem_2_matches_oracle() {
+        oracle::assert_agrees(
+            |rng| {
+                let length = rng.index(15) + 1;
+                vec![(0..length).map(|_| rng.range(0..=9)).collect()]
+            },
+            |banks| {
+                shrink_vec(banks, |bank| {
+                    shrink_vec(bank, |digit| shrink_integer(*digit, 0))
+                })
+            },
+            |banks| problem_2::<i64>(banks).unwrap(),
+            problem_2_oracle,
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{Solution, error, generate::Rng, grid::Grid, parse::ParseError, visualize};
+use std::fmt::Display;
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Grid<char>;
+    const DAY: u8 = 4;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse(input)
+    }
+
+    /// A `size` by `size` grid that is mostly paper.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_grid(rng, size)
+    }
+
+    fn part_1(grid: &Self::Input) -> error::Result<impl Display> {
+        Ok(problem(&mut grid.clone(), false))
+    }
+
+    fn part_2(grid: &Self::Input) -> error::Result<impl Display> {
+        Ok(problem(&mut grid.clone(), true))
+    }
+}
+
+/// Number of accessible rolls of paper, removing them pass after pass until
+/// none are left if `continue_until_stable`. Each pass is recorded as a
+/// `visualize` frame, with the rolls it found marked `x`.
+pub fn problem(grid: &mut Grid<char>, continue_until_stable: bool) -> i64 {
+    let mut accessible_paper = 0;
+    let mut pass = 0;
+    loop {
+        let mut got_changes = false;
+        let mut accessible = Vec::new();
+        for (row, column) in grid.positions() {
+            if grid[(row, column)] != '@' {
+                continue;
+            }
+
+            let count_neighboring_paper = grid
+                .neighbors_8(row, column)
+                .filter(|neighbor| grid[*neighbor] == '@')
+                .count();
+            if count_neighboring_paper < 4 {
+                // Make modifications to grid if `continue_until_stable`.
+                if continue_until_stable {
+                    grid[(row, column)] = '.';
+                    got_changes = true;
+                }
+                accessible_paper += 1;
+                accessible.push((row, column));
+            }
+        }
+
+        pass += 1;
+        visualize::frame(
+            || {
+                format!(
+                    "pass {}: {} accessible rolls ({} in total)",
+                    pass,
+                    accessible.len(),
+                    accessible_paper
+                )
+            },
+            || {
+                let mut cells = grid.clone();
+                for position in &accessible {
+                    cells[*position] = 'x';
+                }
+                cells
+            },
+        );
+
+        // Break if `continue_until_stable` is not set or no changes were made.
+        if !continue_until_stable || !got_changes {
+            break;
+        }
+    }
+
+    accessible_paper
+}
+
+fn generate_grid(rng: &mut Rng, size: usize) -> String {
+    let mut grid = Grid::new(size, size, '.');
+    for position in grid.positions() {
+        if rng.chance(0.6) {
+            grid[position] = '@';
+        }
+    }
+
+    grid.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.
+";
+
+    #[test]
+    fn example() {
+        let grid = Day4::parse(EXAMPLE).unwrap();
+        assert_eq!(problem(&mut grid.clone(), false), 13);
+        assert_eq!(problem(&mut grid.clone(), true), 43);
+    }
+
+    #[test]
+    fn records_each_pass() {
+        let grid = Day4::parse(EXAMPLE).unwrap();
+        let (accessible, recording) = visualize::record(100, || problem(&mut grid.clone(), false));
+        assert_eq!(accessible, 13);
+        let cells = &recording.frames[0].cells;
+        let marked = cells.positions().filter(|position| cells[*position] == 'x');
+        assert_eq!(marked.count(), 13);
+
+        let (removed, recording) = visualize::record(100, || problem(&mut grid.clone(), true));
+        assert_eq!(removed, 43);
+        // The last pass finds nothing left to remove.
+        let last = recording.frames.last().unwrap();
+        let passes = recording.frames.len();
+        let caption = format!("pass {}: 0 accessible rolls (43 in total)", passes);
+        assert_eq!(last.caption, caption);
+    }
+}
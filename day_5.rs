@@ -0,0 +1,129 @@
+use crate::{
+    Solution, error,
+    generate::Rng,
+    parse::{ParseContext, ParseError},
+    range::{Range, RangeSet},
+};
+use std::fmt::Display;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = (RangeSet, Vec<Ingredient>);
+    const DAY: u8 = 5;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_ranges_and_ingredients(input)
+    }
+
+    /// `size` (at least one) possibly overlapping ranges and as many
+    /// ingredients, all below `1000 * size`.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_ranges_and_ingredients(rng, size.max(1))
+    }
+
+    fn part_1((ranges, ingredients): &Self::Input) -> error::Result<impl Display> {
+        Ok(problem_1(ranges, ingredients))
+    }
+
+    fn part_2((ranges, _): &Self::Input) -> error::Result<impl Display> {
+        Ok(problem_2(ranges))
+    }
+}
+
+pub fn problem_1(ranges: &RangeSet, ingredients: &[Ingredient]) -> i64 {
+    let mut fresh_count = 0;
+    for ingredient in ingredients {
+        if ranges.contains(*ingredient) {
+            fresh_count += 1;
+        }
+    }
+
+    fresh_count
+}
+
+pub fn problem_2(ranges: &RangeSet) -> i64 {
+    ranges.total_length()
+}
+
+pub type Ingredient = i64;
+
+pub fn parse_ranges_and_ingredients(
+    input: &str,
+) -> Result<(RangeSet, Vec<Ingredient>), ParseError> {
+    let [ranges_str, ingredients_str]: [&str; 2] =
+        crate::split_blocks(input).try_into().map_err(|_| {
+            ParseError::at_end(
+                input,
+                "expected ranges and ingredients separated by a blank line",
+            )
+        })?;
+    let ranges = ranges_str
+        .lines()
+        .map(|range| {
+            range
+                .parse::<Range>()
+                .map_err(|error| error.within(input, range))
+        })
+        .collect::<Result<RangeSet, ParseError>>()?;
+    let ingredients = ingredients_str
+        .lines()
+        .map(|ingredient| {
+            ingredient
+                .parse::<Ingredient>()
+                .context_at(input, ingredient, "invalid ingredient")
+        })
+        .collect::<Result<Vec<Ingredient>, ParseError>>()?;
+    Ok((ranges, ingredients))
+}
+
+fn generate_ranges_and_ingredients(rng: &mut Rng, count: usize) -> String {
+    let limit = 1000 * count as i64;
+    let mut input = String::new();
+    for _ in 0..count {
+        let start = rng.range(1..=limit);
+        let end = rng.range(start..=(start + 100).min(limit));
+        input.push_str(&format!("{}-{}\n", start, end));
+    }
+    input.push('\n');
+    for _ in 0..count {
+        input.push_str(&format!("{}\n", rng.range(1..=limit)));
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+3-5
+10-14
+16-20
+12-18
+
+1
+5
+8
+11
+17
+32
+";
+
+    #[test]
+    fn example() {
+        let (ranges, ingredients) = Day5::parse(EXAMPLE).unwrap();
+        assert_eq!(problem_1(&ranges, &ingredients), 3);
+        assert_eq!(problem_2(&ranges), 14);
+    }
+
+    #[test]
+    fn merges_nested_and_adjacent_ranges() {
+        let ranges = [(1, 10), (3, 4), (11, 12)]
+            .into_iter()
+            .map(|(start, end)| Range::new(start, end).unwrap())
+            .collect();
+        assert_eq!(problem_2(&ranges), 12);
+    }
+}
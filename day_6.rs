@@ -0,0 +1,268 @@
+use crate::{
+    Solution, error,
+    generate::Rng,
+    num::{self, Integer},
+    parse::{ParseContext, ParseError},
+};
+use std::fmt::Display;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<Problem>;
+    const DAY: u8 = 6;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_problems(input)
+    }
+
+    /// `size` (at least one) problems of two to four numbers with up to four
+    /// digits, each problem left or right aligned.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_problems(rng, size.max(1))
+    }
+
+    fn part_1(problems: &Self::Input) -> error::Result<impl Display> {
+        num::in_mode(
+            || problem_1::<i64>(problems),
+            || problem_1::<i128>(problems),
+        )
+    }
+
+    fn part_2(problems: &Self::Input) -> error::Result<impl Display> {
+        num::in_mode(
+            || problem_2::<i64>(problems),
+            || problem_2::<i128>(problems),
+        )
+    }
+}
+
+pub fn problem_1<N: Integer>(problems: &[Problem]) -> error::Result<N> {
+    let mut solution_total = N::ZERO;
+    for problem in problems {
+        solution_total = solution_total.try_add(problem.solve()?)?;
+    }
+
+    Ok(solution_total)
+}
+
+pub fn problem_2<N: Integer>(problems: &[Problem]) -> error::Result<N> {
+    let mut solution_total = N::ZERO;
+    for problem in problems {
+        solution_total = solution_total.try_add(problem.solve_vertical()?)?;
+    }
+
+    Ok(solution_total)
+}
+
+pub struct Problem {
+    grid_width: usize,
+    values: Vec<i64>,
+    /// Digits of each vertical value, most significant first. They are only
+    /// turned into numbers when solving, so that tall columns can overflow
+    /// the solving type rather than the parser's.
+    values_vertical: Vec<String>,
+    operation: Operation,
+}
+
+impl Problem {
+    pub fn solve<N: Integer>(&self) -> error::Result<N> {
+        let values = self
+            .values
+            .iter()
+            .map(|value| N::try_from_i64(*value))
+            .collect::<error::Result<Vec<N>>>()?;
+        self.operation.reduce(values)
+    }
+
+    pub fn solve_vertical<N: Integer>(&self) -> error::Result<N> {
+        let ten = N::try_from_u64(10)?;
+        let values = self
+            .values_vertical
+            .iter()
+            .map(|digits| {
+                digits.bytes().try_fold(N::ZERO, |value, digit| {
+                    value
+                        .try_mul(ten)?
+                        .try_add(N::try_from_u64((digit - b'0') as u64)?)
+                })
+            })
+            .collect::<error::Result<Vec<N>>>()?;
+        self.operation.reduce(values)
+    }
+}
+
+/// How a problem combines its values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Add,
+    Multiply,
+}
+
+impl Operation {
+    fn reduce<N: Integer>(self, values: Vec<N>) -> error::Result<N> {
+        match self {
+            Operation::Add => N::try_sum(values),
+            Operation::Multiply => N::try_product(values),
+        }
+    }
+}
+
+pub fn parse_problems(input: &str) -> Result<Vec<Problem>, ParseError> {
+    let lines: Vec<&str> = input.lines().collect::<Vec<&str>>();
+    let mut problems: Vec<Problem> = Vec::new();
+    let operations = lines
+        .last()
+        .context_at(input, input, "missing operations line")?;
+
+    // Operation spacing is used to constrain parsing of vertical values.
+    let mut operation = None;
+    let mut spaces_current = 0;
+    for char in operations.chars() {
+        if char == ' ' {
+            spaces_current += 1;
+        } else {
+            if let Some(operation) = operation {
+                let grid_width = spaces_current;
+                problems.push(Problem {
+                    grid_width,
+                    values: vec![0; lines.len() - 1],
+                    values_vertical: vec![String::new(); grid_width],
+                    operation,
+                });
+            }
+            if char == '*' {
+                operation = Some(Operation::Multiply);
+            } else if char == '+' {
+                operation = Some(Operation::Add);
+            }
+            spaces_current = 0
+        }
+    }
+    // Duplicate code to push the final problem.
+    if let Some(operation) = operation {
+        // Plus one to account for there not being another operation (and
+        // therefore no extra space counted) after the last operation.
+        let grid_width = spaces_current + 1;
+        problems.push(Problem {
+            grid_width,
+            values: vec![0; lines.len() - 1],
+            values_vertical: vec![String::new(); grid_width],
+            operation,
+        });
+    }
+
+    // Parse each of the value lines horizontally and vertically.
+    for (i, line) in lines.iter().enumerate().take(lines.len() - 1) {
+        // Parse the horizontal values (just based on a whitespace split).
+        for (j, value) in line.split_whitespace().enumerate() {
+            let problem = problems
+                .get_mut(j)
+                .context_at(input, value, "value has no operation")?;
+            problem.values[i] = value.parse().context_at(input, value, "invalid number")?;
+        }
+
+        let mut scan_index = 0;
+        for problem in problems.iter_mut() {
+            if scan_index >= line.len() {
+                break;
+            }
+
+            // Parse the vertical values (skip spaces and append the digits
+            // of each value line).
+            for position in scan_index..(scan_index + problem.grid_width) {
+                let problem_index = problem.values_vertical.len() - (position - scan_index) - 1;
+                // Editors may strip the trailing spaces of the last column.
+                let byte = line.as_bytes().get(position).copied().unwrap_or(b' ');
+                if byte == b' ' {
+                    continue;
+                }
+                if !byte.is_ascii_digit() {
+                    let token = line.get(position..position + 1).unwrap_or(line);
+                    return Err(ParseError::at(input, token, "expected a digit"));
+                }
+
+                problem.values_vertical[problem_index].push(byte as char);
+            }
+
+            scan_index += problem.grid_width + 1;
+        }
+    }
+
+    Ok(problems)
+}
+
+fn generate_problems(rng: &mut Rng, count: usize) -> String {
+    let rows = rng.index(3) + 2;
+    let mut lines = vec![Vec::new(); rows + 1];
+    for _ in 0..count {
+        let values = (0..rows)
+            .map(|_| {
+                let digits = rng.index(4) as u32 + 1;
+                rng.range(10_i64.pow(digits - 1)..=10_i64.pow(digits) - 1)
+                    .to_string()
+            })
+            .collect::<Vec<String>>();
+        let width = values.iter().map(String::len).max().unwrap_or(1);
+        let left_aligned = rng.chance(0.5);
+        for (line, value) in lines.iter_mut().zip(&values) {
+            line.push(if left_aligned {
+                format!("{:<width$}", value)
+            } else {
+                format!("{:>width$}", value)
+            });
+        }
+        lines[rows].push(format!("{:<width$}", rng.choose(&['+', '*'])));
+    }
+
+    lines
+        .iter()
+        .map(|columns| format!("{}\n", columns.join(" ")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Trailing spaces are significant for the vertical reading.
+    const EXAMPLE: &str = concat!(
+        "123 328  51 64 \n",
+        " 45 64  387 23 \n",
+        "  6 98  215 314\n",
+        "*   +   *   +  \n",
+    );
+
+    #[test]
+    fn example() {
+        let problems = Day6::parse(EXAMPLE).unwrap();
+        assert_eq!(problem_1::<i64>(&problems).unwrap(), 4277556);
+        assert_eq!(problem_2::<i64>(&problems).unwrap(), 3263827);
+    }
+
+    #[test]
+    fn products_beyond_i64() {
+        // The operation line's padding sets the width of the vertical grid.
+        let problems = Day6::parse("9999999999\n9999999999\n*         \n").unwrap();
+        assert!(matches!(
+            problem_1::<i64>(&problems),
+            Err(error::Error::Overflow(_))
+        ));
+        assert_eq!(problem_1::<i128>(&problems).unwrap(), 99999999980000000001);
+        // Ten columns of 99.
+        assert_eq!(problem_2::<i128>(&problems).unwrap(), 90438207500880449001);
+    }
+
+    #[test]
+    fn tall_columns_beyond_i64() {
+        // A single column of twenty 9s reads vertically as a 20-digit number.
+        let input = format!("{}+\n", "9\n".repeat(20));
+        let problems = Day6::parse(&input).unwrap();
+        assert!(matches!(
+            problem_2::<i64>(&problems),
+            Err(error::Error::Overflow(_))
+        ));
+        assert_eq!(problem_2::<i128>(&problems).unwrap(), 99999999999999999999);
+        assert_eq!(problem_1::<i64>(&problems).unwrap(), 180);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{
+    Solution, error,
+    generate::Rng,
+    grid::Grid,
+    num::{self, Integer},
+    parse::{ParseContext, ParseError},
+    visualize,
+};
+use std::fmt::Display;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = (Grid<char>, (usize, usize));
+    const DAY: u8 = 7;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_grid(input)
+    }
+
+    /// A manifold `2 * size + 1` columns wide and `2 * size + 2` rows tall,
+    /// with splitters on every other row and never side by side.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_manifold(rng, size)
+    }
+
+    fn part_1((grid, start): &Self::Input) -> error::Result<impl Display> {
+        Ok(problem_1(&mut grid.clone(), *start))
+    }
+
+    fn part_2((grid, start): &Self::Input) -> error::Result<impl Display> {
+        num::in_mode(
+            || problem_2::<i64>(&mut grid.clone(), *start),
+            || problem_2::<i128>(&mut grid.clone(), *start),
+        )
+    }
+}
+
+/// Number of splits, drawing the beams into `grid` as `|` cells. Each row is
+/// recorded as a `visualize` frame once its beams are drawn.
+pub fn problem_1(grid: &mut Grid<char>, start: (usize, usize)) -> i64 {
+    let mut split_count = 0;
+    for row_current in (start.0 as i64 + 1)..grid.rows() as i64 {
+        for column in 0..grid.columns() as i64 {
+            let current_char = grid.get_or(row_current, column, '.');
+            let above_char = grid.get_or(row_current - 1, column, '.');
+
+            if current_char == '^' && (above_char == 'S' || above_char == '|') {
+                grid.set(row_current, column - 1, '|');
+                grid.set(row_current, column + 1, '|');
+                split_count += 1;
+            } else if above_char == 'S' || above_char == '|' {
+                grid.set(row_current, column, '|');
+            }
+        }
+
+        visualize::frame(
+            || format!("row {}: {} splits", row_current, split_count),
+            || grid.clone(),
+        );
+    }
+
+    split_count
+}
+
+/// Number of timelines, which doubles with every split and so outgrows `i64`
+/// on deep enough manifolds.
+pub fn problem_2<N: Integer>(grid: &mut Grid<char>, start: (usize, usize)) -> error::Result<N> {
+    // Every beam cell gets a count as it is written, so only the start needs
+    // seeding.
+    let mut path_counts = Grid::new(grid.rows(), grid.columns(), N::ZERO);
+    path_counts[start] = N::ONE;
+    for row_current in (start.0 as i64 + 1)..grid.rows() as i64 {
+        for column in 0..grid.columns() as i64 {
+            let current_char = grid.get_or(row_current, column, '.');
+            let above_char = grid.get_or(row_current - 1, column, '.');
+            let above_path_count = path_counts.get_or(row_current - 1, column, N::ZERO);
+
+            if current_char == '^' && (above_char == 'S' || above_char == '|') {
+                grid.set(row_current, column - 1, '|');
+                grid.set(row_current, column + 1, '|');
+                if let Some(count) = path_counts.get_mut(row_current, column - 1) {
+                    *count = count.try_add(above_path_count)?;
+                }
+                if let Some(count) = path_counts.get_mut(row_current, column + 1) {
+                    *count = count.try_add(above_path_count)?;
+                }
+            } else if above_char == 'S' || above_char == '|' {
+                grid.set(row_current, column, '|');
+                let count = &mut path_counts[(row_current as usize, column as usize)];
+                *count = count.try_add(above_path_count)?;
+            }
+        }
+    }
+
+    let last_row = path_counts.rows() - 1;
+    N::try_sum((0..path_counts.columns()).map(|column| path_counts[(last_row, column)]))
+}
+
+pub fn parse_grid(input: &str) -> Result<(Grid<char>, (usize, usize)), ParseError> {
+    let grid = Grid::parse(input)?;
+    let start = grid
+        .find(|cell| *cell == 'S')
+        .context_at(input, input, "start not found")?;
+    Ok((grid, start))
+}
+
+fn generate_manifold(rng: &mut Rng, size: usize) -> String {
+    let (rows, columns) = (2 * size + 2, 2 * size + 1);
+    let mut grid = Grid::new(rows, columns, '.');
+    grid[(0, rng.index(columns))] = 'S';
+    for row in (2..rows).step_by(2) {
+        for column in 0..columns {
+            if grid.get_or(row as i64, column as i64 - 1, '.') != '^' && rng.chance(0.4) {
+                grid[(row, column)] = '^';
+            }
+        }
+    }
+
+    grid.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+.......S.......
+...............
+.......^.......
+...............
+......^.^......
+...............
+.....^.^.^.....
+...............
+....^.^...^....
+...............
+...^.^...^.^...
+...............
+..^...^.....^..
+...............
+.^.^.^.^.^...^.
+...............
+";
+
+    #[test]
+    fn example() {
+        let (grid, start) = Day7::parse(EXAMPLE).unwrap();
+        assert_eq!(start, (0, 7));
+        assert_eq!(problem_1(&mut grid.clone(), start), 21);
+        assert_eq!(problem_2::<i64>(&mut grid.clone(), start).unwrap(), 40);
+    }
+
+    #[test]
+    fn timelines_beyond_i64() {
+        let input = Day7::generate(&mut Rng::new(1), 200);
+        let (grid, start) = Day7::parse(&input).unwrap();
+        assert!(matches!(
+            problem_2::<i64>(&mut grid.clone(), start),
+            Err(error::Error::Overflow(_))
+        ));
+        assert_eq!(
+            problem_2::<i128>(&mut grid.clone(), start).unwrap(),
+            2809187233175204139926
+        );
+    }
+
+    #[test]
+    fn requires_start() {
+        assert!(Day7::parse("...\n.^.\n").is_err());
+    }
+}
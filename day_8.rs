@@ -0,0 +1,166 @@
+use crate::{
+    Solution, budget, error,
+    generate::Rng,
+    geometry::{self, Point3},
+    graph::UnionFind,
+    metrics,
+    num::{self, Integer},
+    parse::ParseError,
+};
+use std::{collections::HashSet, fmt::Display};
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<Point3>;
+    const DAY: u8 = 8;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_coordinates(input)
+    }
+
+    /// `size` (three to 1000) clusters of 30 to 60 distinct points, with
+    /// coordinates below 100000.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_coordinates(rng, size.clamp(3, 1000))
+    }
+
+    fn part_1(coordinates: &Self::Input) -> error::Result<impl Display> {
+        num::in_mode(
+            || problem::<i64>(coordinates, Some(1000)).map(|answers| answers.0),
+            || problem::<i128>(coordinates, Some(1000)).map(|answers| answers.0),
+        )
+    }
+
+    fn part_2(coordinates: &Self::Input) -> error::Result<impl Display> {
+        num::in_mode(
+            || problem::<i64>(coordinates, None).map(|answers| answers.1),
+            || problem::<i128>(coordinates, None).map(|answers| answers.1),
+        )
+    }
+}
+
+pub fn problem<N: Integer>(
+    coordinates: &[Point3],
+    pairs_to_consider: Option<usize>,
+) -> error::Result<(N, N)> {
+    let mut pairs = Vec::new();
+    for i in 0..coordinates.len() {
+        budget::step((coordinates.len() - i - 1) as u64)?;
+        for j in i + 1..coordinates.len() {
+            pairs.push((coordinates[i].distance_squared(&coordinates[j]), i, j));
+        }
+    }
+
+    // Each circuit is a set of coordinate indices.
+    let mut circuits = UnionFind::new(coordinates.len());
+    let mut last_joined_x_product = N::ZERO;
+    let mut pairs_considered = 0;
+    let mut circuits_joined = 0;
+    metrics::gauge("pairs", pairs.len() as i64);
+    pairs.sort();
+    for (_, first, second) in pairs {
+        if let Some(pairs_to_consider) = pairs_to_consider
+            && pairs_considered >= pairs_to_consider
+        {
+            break;
+        }
+        pairs_considered += 1;
+
+        if !circuits.union(first, second) {
+            continue;
+        }
+
+        circuits_joined += 1;
+        last_joined_x_product = N::try_from_i64(coordinates[first].x)?
+            .try_mul(N::try_from_i64(coordinates[second].x)?)?;
+
+        // Stop once every coordinate is in one circuit.
+        if circuits.sets() == 1 {
+            break;
+        }
+    }
+
+    metrics::count("pairs_considered", pairs_considered as u64);
+    metrics::count("circuits_joined", circuits_joined);
+
+    let mut top_counts = circuits.set_sizes();
+    top_counts.sort();
+    top_counts.reverse();
+    let top_product = if top_counts.len() >= 3 {
+        N::try_product(
+            top_counts[..3]
+                .iter()
+                .map(|count| N::try_from_u64(*count as u64))
+                .collect::<error::Result<Vec<N>>>()?,
+        )?
+    } else {
+        N::ZERO
+    };
+
+    Ok((top_product, last_joined_x_product))
+}
+
+pub fn parse_coordinates(input: &str) -> Result<Vec<Point3>, ParseError> {
+    geometry::parse_points(input)
+}
+
+/// Clusters are centred in distinct cells of a 10x10x10 lattice, far enough
+/// apart that every pair within a cluster is shorter than any pair across
+/// two. Each cluster holds at least 435 pairs, so the 1000 pairs of part 1
+/// stay within the clusters and leave at least three circuits.
+fn generate_coordinates(rng: &mut Rng, clusters: usize) -> String {
+    let mut seen = HashSet::new();
+    let mut coordinates = String::new();
+    for center in rng.distinct(0..=999, clusters) {
+        let center = (center / 100, center / 10 % 10, center % 10);
+        let center = [center.0, center.1, center.2].map(|cell| cell * 10000 + 5000);
+        let mut count = rng.range(30..=60);
+        while count > 0 {
+            let coordinate = center.map(|axis| axis + rng.range(-1000..=1000));
+            if seen.insert(coordinate) {
+                let [x, y, z] = coordinate;
+                coordinates.push_str(&format!("{},{},{}\n", x, y, z));
+                count -= 1;
+            }
+        }
+    }
+
+    coordinates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+162,817,812
+57,618,57
+906,360,560
+592,479,940
+352,342,300
+466,668,158
+542,29,236
+431,825,988
+739,650,466
+52,470,668
+216,146,977
+819,987,18
+117,168,530
+805,96,715
+346,949,466
+970,615,88
+941,993,340
+862,61,35
+984,92,344
+425,690,689
+";
+
+    #[test]
+    fn example() {
+        let coordinates = Day8::parse(EXAMPLE).unwrap();
+        // The example only connects the ten closest pairs.
+        assert_eq!(problem::<i64>(&coordinates, Some(10)).unwrap().0, 40);
+        assert_eq!(problem::<i64>(&coordinates, None).unwrap().1, 25272);
+    }
+}
@@ -0,0 +1,391 @@
+use crate::{
+    Solution, budget, error,
+    generate::Rng,
+    geometry::{self, Point2, Rect},
+    grid::Grid,
+    metrics,
+    parse::ParseError,
+};
+use std::fmt::Display;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<Point2>;
+    const DAY: u8 = 9;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_coordinates(input)
+    }
+
+    /// A rectilinear polygon made of `size` (at least two) vertical strips,
+    /// each overlapping the next, with coordinates below `100 * size`.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_polygon(rng, size.max(2))
+    }
+
+    fn part_1(coordinates: &Self::Input) -> error::Result<impl Display> {
+        Ok(problem_1(coordinates))
+    }
+
+    fn part_2(coordinates: &Self::Input) -> error::Result<impl Display> {
+        problem_2(coordinates)
+    }
+}
+
+pub fn problem_1(coordinates: &[Point2]) -> i64 {
+    let mut max_area = 0;
+    for i in 0..coordinates.len() {
+        for j in i + 1..coordinates.len() {
+            let area = Rect::from_corners(coordinates[i], coordinates[j]).area();
+            if area > max_area {
+                max_area = area;
+            }
+        }
+    }
+
+    max_area
+}
+
+/// Rows and columns are compressed into a small grid in which each cell stands
+/// for a band of tiles: odd indices are the rows (or columns) that hold red
+/// tiles, and even indices are the gaps around them. The outline is drawn on
+/// that grid and everything reachable from the border without crossing it is
+/// outside the polygon; a rectangle is valid if it covers no outside tiles.
+/// Each cell filled and each pair of red tiles is one step of the armed
+/// budget.
+pub fn problem_2(coordinates: &[Point2]) -> error::Result<i64> {
+    let rows = Axis::new(coordinates.iter().map(|coordinate| coordinate.y));
+    let columns = Axis::new(coordinates.iter().map(|coordinate| coordinate.x));
+    let compress =
+        |coordinate: Point2| Point2::new(columns.index(coordinate.x), rows.index(coordinate.y));
+
+    metrics::gauge("compressed_cells", (rows.len() * columns.len()) as i64);
+    let mut outline = Grid::new(rows.len(), columns.len(), false);
+    for edge in geometry::polygon_edges(coordinates) {
+        for cell in Rect::from_corners(compress(edge.start), compress(edge.end)).points() {
+            outline[(cell.y, cell.x)] = true;
+        }
+    }
+
+    // The first band is padding before every red tile, so it is outside.
+    let mut outside = Grid::new(rows.len(), columns.len(), false);
+    outside[(0, 0)] = true;
+    let mut stack = vec![(0, 0)];
+    while let Some((row, column)) = stack.pop() {
+        budget::step(1)?;
+        for neighbor in outline.neighbors_4(row, column) {
+            if !outline[neighbor] && !outside[neighbor] {
+                outside[neighbor] = true;
+                stack.push(neighbor);
+            }
+        }
+    }
+
+    // `outside_before[(r, c)]` counts outside cells above and left of `(r, c)`
+    // that hold at least one tile; an empty gap between adjacent rows or
+    // columns can be outside without excluding any rectangle.
+    let mut outside_before = Grid::new(rows.len() + 1, columns.len() + 1, 0);
+    for (row, column) in outside.positions() {
+        let tiles = outside[(row, column)] && rows.has_tiles(row) && columns.has_tiles(column);
+        outside_before[(row + 1, column + 1)] =
+            tiles as i64 + outside_before[(row, column + 1)] + outside_before[(row + 1, column)]
+                - outside_before[(row, column)];
+    }
+
+    let mut max_area = 0;
+    for i in 0..coordinates.len() {
+        budget::step((coordinates.len() - i - 1) as u64)?;
+        for j in i + 1..coordinates.len() {
+            let rect = Rect::from_corners(coordinates[i], coordinates[j]);
+            let cells = Rect::from_corners(compress(rect.min), compress(rect.max));
+
+            let (top, bottom) = (cells.min.y, cells.max.y + 1);
+            let (left, right) = (cells.min.x, cells.max.x + 1);
+            let outside_cells = outside_before[(bottom, right)]
+                - outside_before[(top, right)]
+                - outside_before[(bottom, left)]
+                + outside_before[(top, left)];
+            if outside_cells > 0 {
+                continue;
+            }
+
+            let area = rect.area();
+            if area > max_area {
+                max_area = area;
+            }
+        }
+    }
+
+    Ok(max_area)
+}
+
+/// Distinct values of one coordinate, for compressing it.
+struct Axis {
+    values: Vec<i64>,
+}
+
+impl Axis {
+    fn new(values: impl Iterator<Item = i64>) -> Self {
+        let mut values = values.collect::<Vec<i64>>();
+        values.sort();
+        values.dedup();
+        Self { values }
+    }
+
+    /// Number of compressed bands, including a padding band at each end.
+    fn len(&self) -> usize {
+        2 * self.values.len() + 1
+    }
+
+    fn index(&self, value: i64) -> usize {
+        2 * self
+            .values
+            .binary_search(&value)
+            .expect("value is on the axis")
+            + 1
+    }
+
+    /// Whether the band at `index` holds any tiles; the gap between two
+    /// adjacent values does not.
+    fn has_tiles(&self, index: usize) -> bool {
+        if index % 2 == 1 || index == 0 || index == self.len() - 1 {
+            return true;
+        }
+
+        self.values[index / 2] - self.values[index / 2 - 1] > 1
+    }
+}
+
+pub fn parse_coordinates(input: &str) -> Result<Vec<Point2>, ParseError> {
+    geometry::parse_points(input)
+}
+
+fn generate_polygon(rng: &mut Rng, strips: usize) -> String {
+    let limit = 100 * strips as i64;
+    let mut columns = rng.distinct(0..=limit, strips + 1);
+    columns.sort();
+
+    // Bottom and top rows of each strip. Consecutive strips overlap so the
+    // outline stays simple, and never share a bottom or top row so that
+    // every listed vertex is a real corner. Keeping bottoms below `limit - 1`
+    // and tops above 1 guarantees that a next strip exists.
+    let mut bottoms = vec![rng.range(0..=limit - 2)];
+    let mut tops = vec![rng.range((bottoms[0] + 1).max(2)..=limit)];
+    while bottoms.len() < strips {
+        let (bottom, top) = (bottoms[bottoms.len() - 1], tops[tops.len() - 1]);
+        let next_bottom = rng.range(0..=(top - 1).min(limit - 2));
+        let next_top = rng.range((bottom.max(next_bottom) + 1).max(2)..=limit);
+        if next_bottom != bottom && next_top != top {
+            bottoms.push(next_bottom);
+            tops.push(next_top);
+        }
+    }
+
+    // Walk the bottom edge left to right, then the top edge back.
+    let mut corners = vec![(columns[0], bottoms[0])];
+    for strip in 1..strips {
+        corners.push((columns[strip], bottoms[strip - 1]));
+        corners.push((columns[strip], bottoms[strip]));
+    }
+    corners.push((columns[strips], bottoms[strips - 1]));
+    corners.push((columns[strips], tops[strips - 1]));
+    for strip in (1..strips).rev() {
+        corners.push((columns[strip], tops[strip]));
+        corners.push((columns[strip], tops[strip - 1]));
+    }
+    corners.push((columns[0], tops[0]));
+
+    corners
+        .iter()
+        .map(|(column, row)| format!("{},{}\n", column, row))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{geometry::Segment, oracle};
+    use std::{
+        cmp::{max, min},
+        collections::{BTreeSet, HashSet},
+    };
+
+    const EXAMPLE: &str = "\
+7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3
+";
+
+    #[test]
+    fn example() {
+        let coordinates = Day9::parse(EXAMPLE).unwrap();
+        assert_eq!(problem_1(&coordinates), 50);
+        assert_eq!(problem_2(&coordinates).unwrap(), 24);
+    }
+
+    #[test]
+    fn rectangle_across_adjacent_edges() {
+        // The notch between rows 3 and 4 holds no tiles, so 0,2 to 4,4 is
+        // entirely red or green.
+        let coordinates =
+            Day9::parse("0,2\n2,2\n2,1\n3,1\n3,0\n4,0\n4,4\n3,4\n3,3\n2,3\n2,5\n0,5\n").unwrap();
+        assert_eq!(problem_2(&coordinates).unwrap(), 15);
+    }
+
+    #[test]
+    fn rectangle_across_notch_opening() {
+        // Rectangles across the mouth of a notch cross no edge but still
+        // cover tiles outside the outline.
+        let coordinates = Day9::parse(
+            "0,1\n1,1\n1,0\n2,0\n2,2\n3,2\n3,5\n4,5\n4,6\n3,6\n3,7\n2,7\n2,3\n1,3\n1,4\n0,4\n",
+        )
+        .unwrap();
+        assert_eq!(problem_2(&coordinates).unwrap(), 12);
+    }
+
+    #[test]
+    fn edge_nested_inside_rectangle() {
+        // The bottom of the notch from 4,0 to 6,5 lies strictly inside the
+        // span of the corner-to-corner rectangle, which must not count as
+        // filled.
+        let coordinates = Day9::parse("0,0\n4,0\n4,5\n6,5\n6,0\n10,0\n10,10\n0,10\n").unwrap();
+        assert_eq!(problem_2(&coordinates).unwrap(), 55);
+        assert_eq!(problem_2_oracle(&coordinates), 55);
+    }
+
+    /// Marks every tile inside or on the outline, then tries every rectangle
+    /// tile by tile.
+    fn problem_2_oracle(coordinates: &[Point2]) -> i64 {
+        let edges = geometry::polygon_edges(coordinates).collect::<Vec<Segment>>();
+        let on_edge = |row: i64, column: i64| {
+            edges
+                .iter()
+                .any(|edge| edge.contains(&Point2::new(column, row)))
+        };
+        // Cast a ray to the right and count the vertical edges it crosses.
+        let inside = |row: i64, column: i64| {
+            let crossings = edges
+                .iter()
+                .filter(|edge| {
+                    let (a, b) = (edge.start, edge.end);
+                    edge.is_vertical()
+                        && a.x > column
+                        && min(a.y, b.y) <= row
+                        && row < max(a.y, b.y)
+                })
+                .count();
+            crossings % 2 == 1
+        };
+
+        let mut tiles = HashSet::new();
+        for row in coordinates.iter().map(|coordinate| coordinate.y) {
+            for column in coordinates.iter().map(|coordinate| coordinate.x) {
+                tiles.insert((row, column));
+            }
+        }
+        let rows = coordinates.iter().map(|coordinate| coordinate.y);
+        let columns = coordinates.iter().map(|coordinate| coordinate.x);
+        let (min_row, max_row) = (rows.clone().min().unwrap(), rows.max().unwrap());
+        let (min_column, max_column) = (columns.clone().min().unwrap(), columns.max().unwrap());
+        let allowed = (min_row..=max_row)
+            .flat_map(|row| (min_column..=max_column).map(move |column| (row, column)))
+            .filter(|(row, column)| on_edge(*row, *column) || inside(*row, *column))
+            .collect::<HashSet<_>>();
+
+        let mut max_area = 0;
+        for first in coordinates {
+            for second in coordinates {
+                let rect = Rect::from_corners(*first, *second);
+                let filled = (rect.min.y..=rect.max.y).all(|row| {
+                    (rect.min.x..=rect.max.x).all(|column| allowed.contains(&(row, column)))
+                });
+                if filled {
+                    max_area = max_area.max(rect.area());
+                }
+            }
+        }
+
+        max_area
+    }
+
+    /// Renumbers rows and columns in order with gaps of one or two, keeping
+    /// the shape of the polygon but making the oracle cheap.
+    fn compress(coordinates: Vec<Point2>, rng: &mut Rng) -> Vec<Point2> {
+        let mut renumber = |values: BTreeSet<i64>| {
+            let mut next = 0;
+            values
+                .into_iter()
+                .map(|value| {
+                    next += rng.range(1..=2);
+                    (value, next)
+                })
+                .collect::<Vec<_>>()
+        };
+        let rows = renumber(coordinates.iter().map(|coordinate| coordinate.y).collect());
+        let columns = renumber(coordinates.iter().map(|coordinate| coordinate.x).collect());
+        let lookup = |pairs: &[(i64, i64)], value: i64| {
+            pairs.iter().find(|(old, _)| *old == value).unwrap().1
+        };
+        coordinates
+            .iter()
+            .map(|coordinate| {
+                Point2::new(lookup(&columns, coordinate.x), lookup(&rows, coordinate.y))
+            })
+            .collect()
+    }
+
+    /// Closes up gaps between used rows or columns, one tile at a time.
+    fn shrink_polygon(coordinates: &[Point2]) -> Vec<Vec<Point2>> {
+        let mut candidates = Vec::new();
+        let rows = coordinates
+            .iter()
+            .map(|coordinate| coordinate.y)
+            .collect::<BTreeSet<_>>();
+        for row in rows
+            .iter()
+            .filter(|row| **row > 0 && !rows.contains(&(**row - 1)))
+        {
+            let shifted = coordinates.iter().map(|coordinate| {
+                let shift = (coordinate.y >= *row) as i64;
+                Point2::new(coordinate.x, coordinate.y - shift)
+            });
+            candidates.push(shifted.collect());
+        }
+        let columns = coordinates
+            .iter()
+            .map(|coordinate| coordinate.x)
+            .collect::<BTreeSet<_>>();
+        for column in columns
+            .iter()
+            .filter(|column| **column > 0 && !columns.contains(&(**column - 1)))
+        {
+            let shifted = coordinates.iter().map(|coordinate| {
+                let shift = (coordinate.x >= *column) as i64;
+                Point2::new(coordinate.x - shift, coordinate.y)
+            });
+            candidates.push(shifted.collect());
+        }
+
+        candidates
+    }
+
+    #[test]
+    fn problem_2_matches_oracle() {
+        oracle::assert_agrees(
+            |rng| {
+                let strips = rng.index(4) + 2;
+                let input = generate_polygon(rng, strips);
+                compress(parse_coordinates(&input).unwrap(), rng)
+            },
+            |coordinates| shrink_polygon(coordinates),
+            |coordinates| problem_2(coordinates).unwrap(),
+            |coordinates| problem_2_oracle(coordinates),
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use crate::parse::ParseError;
+use std::{
+    fmt::{self, Display},
+    io,
+    process::ExitCode,
+};
+
+/// Exit code for runs that completed but did not succeed, e.g. a failed
+/// `aoc verify`.
+pub const EXIT_FAILURE: u8 = 1;
+
+/// Exit code for arithmetic overflow (see `num::Integer`).
+pub const EXIT_OVERFLOW: u8 = 7;
+
+/// Exit code for runs where a part ran out of budget, whether or not it
+/// surfaced as an `Error`.
+pub const EXIT_BUDGET_EXCEEDED: u8 = 6;
+
+/// Everything that can stop a day (or the runner) from producing an answer.
+///
+/// Each variant maps to its own process exit code:
+///
+/// | code | variant          |
+/// |------|------------------|
+/// | 2    | `Usage`          |
+/// | 3    | `Io`             |
+/// | 4    | `Parse`          |
+/// | 5    | `Infeasible`     |
+/// | 6    | `BudgetExceeded` |
+/// | 7    | `Overflow`       |
+/// | 101  | `Panic`          |
+#[derive(Debug)]
+pub enum Error {
+    /// Invalid command-line arguments.
+    Usage(String),
+    /// Reading or writing `path` failed.
+    Io { path: String, source: io::Error },
+    /// The input is malformed.
+    Parse(ParseError),
+    /// The input is well-formed but has no answer.
+    Infeasible(String),
+    /// A search gave up before finding an answer.
+    BudgetExceeded(String),
+    /// An answer does not fit in the integer type it is computed in.
+    Overflow(String),
+    /// The solver panicked, which is a bug. Only reported where panics are
+    /// caught (see `batch::isolate`); the code matches an uncaught panic.
+    Panic(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn io(path: impl Display, source: io::Error) -> Self {
+        Error::Io {
+            path: path.to_string(),
+            source,
+        }
+    }
+
+    /// Short name of the variant, for tables and logs.
+    pub fn category(&self) -> &'static str {
+        match self {
+            Error::Usage(_) => "usage",
+            Error::Io { .. } => "io",
+            Error::Parse(_) => "parse",
+            Error::Infeasible(_) => "infeasible",
+            Error::BudgetExceeded(_) => "budget",
+            Error::Overflow(_) => "overflow",
+            Error::Panic(_) => "panic",
+        }
+    }
+
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Error::Usage(_) => 2,
+            Error::Io { .. } => 3,
+            Error::Parse(_) => 4,
+            Error::Infeasible(_) => 5,
+            Error::BudgetExceeded(_) => EXIT_BUDGET_EXCEEDED,
+            Error::Overflow(_) => EXIT_OVERFLOW,
+            Error::Panic(_) => 101,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Usage(message) => write!(f, "{}", message),
+            Error::Io { path, source } => write!(f, "{}: {}", path, source),
+            Error::Parse(error) => write!(f, "{}", error),
+            Error::Infeasible(message) => write!(f, "no solution: {}", message),
+            Error::BudgetExceeded(message) => write!(f, "budget exceeded: {}", message),
+            Error::Overflow(message) => write!(f, "arithmetic overflow: {}", message),
+            Error::Panic(message) => write!(f, "panicked: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Error::Parse(error)
+    }
+}
+
+impl From<&Error> for ExitCode {
+    fn from(error: &Error) -> Self {
+        ExitCode::from(error.exit_code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_codes_are_distinct() {
+        let errors = [
+            Error::Usage(String::new()),
+            Error::io("1.txt", io::Error::from(io::ErrorKind::NotFound)),
+            Error::Parse(ParseError::at_end("", "empty")),
+            Error::Infeasible(String::new()),
+            Error::BudgetExceeded(String::new()),
+            Error::Overflow(String::new()),
+            Error::Panic(String::new()),
+        ];
+        let mut codes = errors.iter().map(Error::exit_code).collect::<Vec<u8>>();
+        codes.push(EXIT_FAILURE);
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes, [1, 2, 3, 4, 5, 6, 7, 101]);
+    }
+}
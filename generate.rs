@@ -0,0 +1,158 @@
+use std::ops::RangeInclusive;
+
+/// Seeded pseudo-random number generator (SplitMix64) for reproducible
+/// generated inputs. Not suitable for anything security related.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94d049bb133111eb);
+        value ^ (value >> 31)
+    }
+
+    /// Uniform value in `0..bound`. Panics if `bound` is zero.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "empty range");
+        // Multiply-shift keeps the bias negligible for the small bounds used
+        // here without a rejection loop.
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+
+    /// Uniform value in an inclusive range. Panics if the range is empty.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range");
+        let span = end.abs_diff(start) + 1;
+        start.wrapping_add(self.below(span) as i64)
+    }
+
+    /// Uniform index into a collection of `length` items.
+    pub fn index(&mut self, length: usize) -> usize {
+        self.below(length as u64) as usize
+    }
+
+    /// True with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        unit < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.index(index + 1));
+        }
+    }
+
+    /// `count` distinct values from `range`, in random order. Panics if the
+    /// range has fewer than `count` values.
+    pub fn distinct(&mut self, range: RangeInclusive<i64>, count: usize) -> Vec<i64> {
+        let span = range.end().abs_diff(*range.start()) + 1;
+        assert!(count as u64 <= span, "range too small");
+        let mut chosen = std::collections::HashSet::new();
+        let mut values = Vec::with_capacity(count);
+        while values.len() < count {
+            let value = self.range(range.clone());
+            if chosen.insert(value) {
+                values.push(value);
+            }
+        }
+
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Solution, Status, day_1, day_2, day_3, day_4, day_5, day_6, day_7, day_8};
+    use crate::{day_9, day_10, day_11, day_12};
+    use std::collections::HashSet;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let (mut a, mut b) = (Rng::new(7), Rng::new(7));
+        let first = (0..8).map(|_| a.next_u64()).collect::<Vec<_>>();
+        assert_eq!(first, (0..8).map(|_| b.next_u64()).collect::<Vec<_>>());
+        let mut c = Rng::new(8);
+        assert_ne!(first, (0..8).map(|_| c.next_u64()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn range_stays_in_bounds() {
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            assert!((-3..=3).contains(&rng.range(-3..=3)));
+        }
+        assert_eq!(rng.range(5..=5), 5);
+        assert!((0..1000).map(|_| rng.range(0..=1)).any(|value| value == 1));
+    }
+
+    #[test]
+    fn distinct_values() {
+        let mut values = Rng::new(3).distinct(1..=10, 10);
+        values.sort();
+        assert_eq!(values, (1..=10).collect::<Vec<_>>());
+    }
+
+    /// Solves a day's generated inputs, failing if any does not parse or
+    /// solve, or if a part gives the same answer on every one of them.
+    fn solves_generated<S: Solution>() {
+        let mut answers = vec![HashSet::new(); S::PARTS as usize];
+        for seed in 0..20 {
+            let input = S::generate(&mut Rng::new(seed), 8);
+            let solved = crate::solve::<S>(&input, None).unwrap_or_else(|error| {
+                panic!("day {} seed {}: {}\n{}", S::DAY, seed, error, input)
+            });
+            for answer in solved {
+                assert_eq!(
+                    answer.status,
+                    Status::Solved,
+                    "day {} seed {}",
+                    S::DAY,
+                    seed
+                );
+                answers[answer.part as usize - 1].insert(answer.value);
+            }
+        }
+
+        for (index, values) in answers.iter().enumerate() {
+            assert!(
+                values.len() > 1,
+                "day {} part {} answers {:?} on every generated input",
+                S::DAY,
+                index + 1,
+                values
+            );
+        }
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        solves_generated::<day_1::Day1>();
+        solves_generated::<day_2::Day2>();
+        solves_generated::<day_3::Day3>();
+        solves_generated::<day_4::Day4>();
+        solves_generated::<day_5::Day5>();
+        solves_generated::<day_6::Day6>();
+        solves_generated::<day_7::Day7>();
+        solves_generated::<day_8::Day8>();
+        solves_generated::<day_9::Day9>();
+        solves_generated::<day_10::Day10>();
+        solves_generated::<day_11::Day11>();
+        solves_generated::<day_12::Day12>();
+    }
+}
@@ -0,0 +1,326 @@
+use crate::parse::{ParseContext, ParseError};
+use std::{
+    cmp::{max, min},
+    fmt::{self, Debug, Display},
+    hash::Hash,
+    ops::{Add, Mul, Sub},
+    str::FromStr,
+};
+
+/// Integer type of point coordinates. Metrics are exact: nothing goes
+/// through floating point.
+pub trait Scalar:
+    Copy
+    + Ord
+    + Hash
+    + Debug
+    + Display
+    + FromStr
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// `|self - other|`, without going negative for unsigned types.
+    fn abs_diff(self, other: Self) -> Self {
+        max(self, other) - min(self, other)
+    }
+}
+
+macro_rules! impl_scalar {
+    ($($type:ty),*) => {$(
+        impl Scalar for $type {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        }
+    )*};
+}
+
+impl_scalar!(i32, i64, i128, u32, u64, usize);
+
+/// Point in the plane, e.g. a tile at column `x` and row `y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2<T = i64> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Scalar> Point2<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// Square of the Euclidean distance, which orders points by distance
+    /// just as the distance itself does.
+    pub fn distance_squared(&self, other: &Self) -> T {
+        let (dx, dy) = (self.x.abs_diff(other.x), self.y.abs_diff(other.y));
+        dx * dx + dy * dy
+    }
+
+    pub fn manhattan(&self, other: &Self) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+/// Point in space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3<T = i64> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Scalar> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Square of the Euclidean distance, which orders points by distance
+    /// just as the distance itself does.
+    pub fn distance_squared(&self, other: &Self) -> T {
+        let (dx, dy, dz) = (
+            self.x.abs_diff(other.x),
+            self.y.abs_diff(other.y),
+            self.z.abs_diff(other.z),
+        );
+        dx * dx + dy * dy + dz * dz
+    }
+
+    pub fn manhattan(&self, other: &Self) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+}
+
+/// Parses exactly `N` comma-separated numbers.
+fn parse_components<T: Scalar, const N: usize>(point: &str) -> Result<[T; N], ParseError>
+where
+    T::Err: Display,
+{
+    point
+        .split(',')
+        .map(|part| part.parse::<T>().context_at(point, part, "invalid number"))
+        .collect::<Result<Vec<T>, ParseError>>()?
+        .try_into()
+        .map_err(|_| {
+            let message = format!("expected {} comma-separated numbers", N);
+            ParseError::at(point, point, message)
+        })
+}
+
+/// Parses `x,y`, e.g. `7,3`. Errors point into the parsed string; use
+/// `ParseError::within` (or `parse_points`) to locate them in a larger input.
+impl<T: Scalar> FromStr for Point2<T>
+where
+    T::Err: Display,
+{
+    type Err = ParseError;
+
+    fn from_str(point: &str) -> Result<Self, ParseError> {
+        let [x, y] = parse_components(point)?;
+        Ok(Self::new(x, y))
+    }
+}
+
+/// Parses `x,y,z`, e.g. `162,817,812`.
+impl<T: Scalar> FromStr for Point3<T>
+where
+    T::Err: Display,
+{
+    type Err = ParseError;
+
+    fn from_str(point: &str) -> Result<Self, ParseError> {
+        let [x, y, z] = parse_components(point)?;
+        Ok(Self::new(x, y, z))
+    }
+}
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// One point per line, such as `Point2` or `Point3`.
+pub fn parse_points<P: FromStr<Err = ParseError>>(input: &str) -> Result<Vec<P>, ParseError> {
+    input
+        .lines()
+        .map(|line| line.parse::<P>().map_err(|error| error.within(input, line)))
+        .collect()
+}
+
+/// Axis-aligned rectangle of integer points, inclusive of both corners
+/// (like `range::Range`), so a single point has area one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect<T = i64> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+impl<T: Scalar> Rect<T> {
+    /// Smallest rectangle containing both (opposite) corners.
+    pub fn from_corners(a: Point2<T>, b: Point2<T>) -> Self {
+        Self {
+            min: Point2::new(min(a.x, b.x), min(a.y, b.y)),
+            max: Point2::new(max(a.x, b.x), max(a.y, b.y)),
+        }
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::ONE
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::ONE
+    }
+
+    /// Number of points in the rectangle.
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, point: &Point2<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    pub fn intersection(&self, other: &Rect<T>) -> Option<Rect<T>> {
+        let low = Point2::new(max(self.min.x, other.min.x), max(self.min.y, other.min.y));
+        let high = Point2::new(min(self.max.x, other.max.x), min(self.max.y, other.max.y));
+        (low.x <= high.x && low.y <= high.y).then_some(Rect {
+            min: low,
+            max: high,
+        })
+    }
+}
+
+impl Rect<usize> {
+    /// Every point in the rectangle, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point2<usize>> + use<> {
+        let (min, max) = (self.min, self.max);
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point2::new(x, y)))
+    }
+}
+
+/// Straight line between two points, such as an edge of a polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment<T = i64> {
+    pub start: Point2<T>,
+    pub end: Point2<T>,
+}
+
+impl<T: Scalar> Segment<T> {
+    pub fn new(start: Point2<T>, end: Point2<T>) -> Self {
+        Self { start, end }
+    }
+
+    /// Whether the segment runs along a row (a single point counts).
+    pub fn is_horizontal(&self) -> bool {
+        self.start.y == self.end.y
+    }
+
+    /// Whether the segment runs along a column (a single point counts).
+    pub fn is_vertical(&self) -> bool {
+        self.start.x == self.end.x
+    }
+
+    pub fn bounds(&self) -> Rect<T> {
+        Rect::from_corners(self.start, self.end)
+    }
+
+    /// Whether `point` lies on the segment, in any direction.
+    pub fn contains(&self, point: &Point2<T>) -> bool {
+        // Within the bounds, `point - start` points the same way as
+        // `end - start` on each axis, so the cross product of the two is zero
+        // exactly when the products of their magnitudes agree.
+        self.bounds().contains(point)
+            && self.end.x.abs_diff(self.start.x) * point.y.abs_diff(self.start.y)
+                == self.end.y.abs_diff(self.start.y) * point.x.abs_diff(self.start.x)
+    }
+}
+
+/// Edges of the closed polygon through `vertices`, including the one from
+/// the last vertex back to the first.
+pub fn polygon_edges<T: Scalar>(vertices: &[Point2<T>]) -> impl Iterator<Item = Segment<T>> + '_ {
+    (0..vertices.len())
+        .map(|index| Segment::new(vertices[index], vertices[(index + 1) % vertices.len()]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metrics() {
+        let (a, b) = (Point2::new(1, 5), Point2::new(4, 1));
+        assert_eq!(a.distance_squared(&b), 25);
+        assert_eq!(a.manhattan(&b), 7);
+        let (a, b) = (Point3::new(0u64, 10, 3), Point3::new(2, 7, 3));
+        assert_eq!(a.distance_squared(&b), 13);
+        assert_eq!(a.manhattan(&b), 5);
+    }
+
+    #[test]
+    fn parses_points() {
+        assert_eq!("7,-3".parse::<Point2>().unwrap(), Point2::new(7, -3));
+        assert_eq!(
+            "162,817,812".parse::<Point3>().unwrap(),
+            Point3::new(162, 817, 812)
+        );
+        assert_eq!(Point3::new(1, 2, 3).to_string(), "1,2,3");
+
+        let error = "1,2".parse::<Point3>().unwrap_err();
+        assert_eq!(error.message, "expected 3 comma-separated numbers");
+        let error = parse_points::<Point2<u32>>("1,2\n3,-4\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert!(error.message.starts_with("invalid number"));
+    }
+
+    #[test]
+    fn rectangles() {
+        let rect = Rect::from_corners(Point2::new(11, 1), Point2::new(2, 5));
+        assert_eq!((rect.width(), rect.height(), rect.area()), (10, 5, 50));
+        assert!(rect.contains(&Point2::new(2, 3)));
+        assert!(!rect.contains(&Point2::new(1, 3)));
+
+        let other = Rect::from_corners(Point2::new(9, 5), Point2::new(20, 7));
+        let overlap = rect.intersection(&other).unwrap();
+        assert_eq!(
+            overlap,
+            Rect::from_corners(Point2::new(9, 5), Point2::new(11, 5))
+        );
+        assert_eq!(overlap.area(), 3);
+        assert_eq!(
+            rect.intersection(&Rect::from_corners(Point2::new(0, 6), Point2::new(20, 6))),
+            None
+        );
+
+        let points = Rect::from_corners(Point2::new(1usize, 0), Point2::new(2, 1)).points();
+        let points = points.map(|point| (point.x, point.y)).collect::<Vec<_>>();
+        assert_eq!(points, [(1, 0), (2, 0), (1, 1), (2, 1)]);
+    }
+
+    #[test]
+    fn segments() {
+        let edge = Segment::new(Point2::new(2, 5), Point2::new(2, 3));
+        assert!(edge.is_vertical() && !edge.is_horizontal());
+        assert!(edge.contains(&Point2::new(2, 4)));
+        assert!(!edge.contains(&Point2::new(2, 6)));
+
+        let diagonal = Segment::new(Point2::new(4u32, 0), Point2::new(0, 8));
+        assert!(diagonal.contains(&Point2::new(2, 4)));
+        assert!(!diagonal.contains(&Point2::new(2, 5)));
+        assert!(!Segment::new(Point2::new(0, 0), Point2::new(2, 2)).contains(&Point2::new(2, 0)));
+
+        let triangle = [Point2::new(0, 0), Point2::new(1, 0), Point2::new(1, 1)];
+        let edges = polygon_edges(&triangle).collect::<Vec<_>>();
+        assert_eq!(edges.len(), 3);
+        assert_eq!(edges[2], Segment::new(Point2::new(1, 1), Point2::new(0, 0)));
+    }
+}
@@ -0,0 +1,412 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::Display,
+    hash::Hash,
+};
+
+/// Index of a node in a `Graph`, assigned in insertion order.
+pub type NodeId = usize;
+
+/// Directed graph whose nodes are interned: each distinct value gets a dense
+/// `NodeId`, so algorithms can use vectors instead of hash maps.
+#[derive(Debug, Clone)]
+pub struct Graph<T> {
+    ids: HashMap<T, NodeId>,
+    nodes: Vec<T>,
+    sinks: Vec<Vec<NodeId>>,
+}
+
+impl<T> Default for Graph<T> {
+    fn default() -> Self {
+        Self {
+            ids: HashMap::new(),
+            nodes: Vec::new(),
+            sinks: Vec::new(),
+        }
+    }
+}
+
+impl<T: Clone + Eq + Hash> Graph<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Id of `node`, adding it without edges if it is new.
+    pub fn intern(&mut self, node: T) -> NodeId {
+        if let Some(id) = self.ids.get(&node) {
+            return *id;
+        }
+
+        let id = self.nodes.len();
+        self.ids.insert(node.clone(), id);
+        self.nodes.push(node);
+        self.sinks.push(Vec::new());
+        id
+    }
+
+    /// Adds an edge from `source` to `sink`, interning both.
+    pub fn add_edge(&mut self, source: T, sink: T) -> (NodeId, NodeId) {
+        let source = self.intern(source);
+        let sink = self.intern(sink);
+        self.sinks[source].push(sink);
+        (source, sink)
+    }
+
+    pub fn id(&self, node: &T) -> Option<NodeId> {
+        self.ids.get(node).copied()
+    }
+}
+
+impl<T> Graph<T> {
+    pub fn node(&self, id: NodeId) -> &T {
+        &self.nodes[id]
+    }
+
+    /// Nodes that `id` has edges to, in the order they were added.
+    pub fn sinks(&self, id: NodeId) -> &[NodeId] {
+        &self.sinks[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.sinks.iter().map(Vec::len).sum()
+    }
+
+    /// Nodes reachable from `source` (including itself) in breadth-first
+    /// order, each with its distance in edges.
+    pub fn bfs(&self, source: NodeId) -> Vec<(NodeId, usize)> {
+        let mut seen = vec![false; self.len()];
+        let mut order = Vec::new();
+        let mut queue = VecDeque::from([(source, 0)]);
+        seen[source] = true;
+        while let Some((node, distance)) = queue.pop_front() {
+            order.push((node, distance));
+            for sink in self.sinks(node) {
+                if !seen[*sink] {
+                    seen[*sink] = true;
+                    queue.push_back((*sink, distance + 1));
+                }
+            }
+        }
+
+        order
+    }
+
+    /// Nodes reachable from `source` (including itself) in depth-first
+    /// preorder.
+    pub fn dfs(&self, source: NodeId) -> Vec<NodeId> {
+        let mut seen = vec![false; self.len()];
+        let mut order = Vec::new();
+        let mut stack = vec![source];
+        while let Some(node) = stack.pop() {
+            if seen[node] {
+                continue;
+            }
+
+            seen[node] = true;
+            order.push(node);
+            // Reversed so that the first sink is visited first.
+            stack.extend(self.sinks(node).iter().rev().filter(|sink| !seen[**sink]));
+        }
+
+        order
+    }
+
+    /// Whether each node is reachable from `source`, indexed by id.
+    pub fn reachable_from(&self, source: NodeId) -> Vec<bool> {
+        let mut reachable = vec![false; self.len()];
+        for node in self.dfs(source) {
+            reachable[node] = true;
+        }
+
+        reachable
+    }
+
+    /// Orders the nodes for which `include` holds so that every node comes
+    /// before its sinks (Kahn's algorithm). Edges to or from excluded nodes
+    /// are ignored. Fails with one of the cycles if there is no such order.
+    pub fn topological_sort(&self, include: impl Fn(NodeId) -> bool) -> Result<Vec<NodeId>, Cycle> {
+        let included = (0..self.len()).map(include).collect::<Vec<bool>>();
+        let edges = || {
+            (0..self.len())
+                .filter(|source| included[*source])
+                .flat_map(|source| self.sinks(source).iter().map(move |sink| (source, *sink)))
+                .filter(|(_, sink)| included[*sink])
+        };
+
+        // Count the number of sources that lead to each sink.
+        let mut source_counts = vec![0; self.len()];
+        for (_, sink) in edges() {
+            source_counts[sink] += 1;
+        }
+
+        // Start with the nodes that have no inbound sources.
+        let mut queue = (0..self.len())
+            .filter(|node| included[*node] && source_counts[*node] == 0)
+            .collect::<VecDeque<NodeId>>();
+        let mut order = Vec::new();
+        while let Some(source) = queue.pop_front() {
+            order.push(source);
+            for sink in self.sinks(source) {
+                if !included[*sink] {
+                    continue;
+                }
+
+                source_counts[*sink] -= 1;
+                if source_counts[*sink] == 0 {
+                    queue.push_back(*sink);
+                }
+            }
+        }
+        if order.len() == included.iter().filter(|included| **included).count() {
+            return Ok(order);
+        }
+
+        // Every node left over has a source that is also left over, so
+        // following sources backwards must eventually repeat a node.
+        let mut left_source = vec![None; self.len()];
+        for (source, sink) in edges() {
+            if source_counts[source] > 0 && source_counts[sink] > 0 {
+                left_source[sink] = Some(source);
+            }
+        }
+        let start = (0..self.len())
+            .find(|node| included[*node] && source_counts[*node] > 0)
+            .expect("a node is left over");
+        let mut path = vec![start];
+        let mut position = HashMap::from([(start, 0)]);
+        loop {
+            let node = left_source[*path.last().unwrap()].expect("left over nodes have sources");
+            if let Some(index) = position.get(&node) {
+                let mut cycle = path.split_off(*index);
+                cycle.reverse();
+                // Start at the earliest node so the report is stable.
+                let earliest = (0..cycle.len()).min_by_key(|index| cycle[*index]).unwrap();
+                cycle.rotate_left(earliest);
+                return Err(Cycle(cycle));
+            }
+            position.insert(node, path.len());
+            path.push(node);
+        }
+    }
+}
+
+/// Nodes along a cycle, each with an edge to the next and the last with an
+/// edge back to the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle(pub Vec<NodeId>);
+
+impl Cycle {
+    /// The cycle as `a -> b -> a`, naming nodes by their values.
+    pub fn describe<T: Display>(&self, graph: &Graph<T>) -> String {
+        self.0
+            .iter()
+            .chain(self.0.first())
+            .map(|node| graph.node(*node).to_string())
+            .collect::<Vec<String>>()
+            .join(" -> ")
+    }
+}
+
+/// Breadth-first search over an implicit graph, for state spaces too large
+/// to build up front. Returns the first state satisfying `is_goal` and its
+/// distance from `start`, or `None` if none is reachable.
+pub fn bfs<S, I>(
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(S, usize)>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut seen = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((state, distance)) = queue.pop_front() {
+        if is_goal(&state) {
+            return Some((state, distance));
+        }
+
+        for neighbor in neighbors(&state) {
+            if seen.insert(neighbor.clone()) {
+                queue.push_back((neighbor, distance + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// Disjoint sets of `0..n` with union by size and path compression.
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    sets: usize,
+}
+
+impl UnionFind {
+    /// `n` singleton sets.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parents: (0..n).collect(),
+            sizes: vec![1; n],
+            sets: n,
+        }
+    }
+
+    /// Representative of the set containing `element`.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        // Point everything on the way directly at the root.
+        let mut element = element;
+        while self.parents[element] != root {
+            let parent = self.parents[element];
+            self.parents[element] = root;
+            element = parent;
+        }
+
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`, returning whether they were
+    /// separate.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        if self.sizes[a] < self.sizes[b] {
+            (a, b) = (b, a);
+        }
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        self.sets -= 1;
+        true
+    }
+
+    /// Size of the set containing `element`.
+    pub fn size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    /// Number of disjoint sets.
+    pub fn sets(&self) -> usize {
+        self.sets
+    }
+
+    /// Size of every set, in no particular order.
+    pub fn set_sizes(&self) -> Vec<usize> {
+        (0..self.parents.len())
+            .filter(|element| self.parents[*element] == *element)
+            .map(|root| self.sizes[root])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&'static str, &[&'static str])]) -> Graph<&'static str> {
+        let mut graph = Graph::new();
+        for (source, sinks) in edges {
+            graph.intern(*source);
+            for sink in *sinks {
+                graph.add_edge(*source, *sink);
+            }
+        }
+
+        graph
+    }
+
+    fn names(graph: &Graph<&'static str>, nodes: &[NodeId]) -> Vec<&'static str> {
+        nodes.iter().map(|node| *graph.node(*node)).collect()
+    }
+
+    #[test]
+    fn interns_nodes() {
+        let mut graph = graph(&[("a", &["b", "c"]), ("b", &["c"])]);
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(graph.intern("b"), 1);
+        assert_eq!(graph.id(&"c"), Some(2));
+        assert_eq!(graph.id(&"d"), None);
+        assert_eq!(graph.sinks(0), [1, 2]);
+    }
+
+    #[test]
+    fn searches() {
+        let graph = graph(&[
+            ("a", &["b", "c"]),
+            ("b", &["d"]),
+            ("c", &["d"]),
+            ("e", &["a"]),
+        ]);
+        let bfs = graph
+            .bfs(0)
+            .into_iter()
+            .map(|(node, distance)| (*graph.node(node), distance))
+            .collect::<Vec<_>>();
+        assert_eq!(bfs, [("a", 0), ("b", 1), ("c", 1), ("d", 2)]);
+        assert_eq!(names(&graph, &graph.dfs(0)), ["a", "b", "d", "c"]);
+        assert_eq!(graph.reachable_from(1), [false, true, false, true, false]);
+    }
+
+    #[test]
+    fn topological_sort_orders_sources_before_sinks() {
+        let graph = graph(&[("a", &["b", "c"]), ("b", &["d"]), ("c", &["d"])]);
+        let order = graph.topological_sort(|_| true).unwrap();
+        assert_eq!(names(&graph, &order), ["a", "b", "c", "d"]);
+
+        let reachable = graph.reachable_from(graph.id(&"b").unwrap());
+        let order = graph.topological_sort(|node| reachable[node]).unwrap();
+        assert_eq!(names(&graph, &order), ["b", "d"]);
+    }
+
+    #[test]
+    fn topological_sort_reports_cycles() {
+        let graph = graph(&[("z", &["x"]), ("x", &["y"]), ("y", &["x", "w"])]);
+        let cycle = graph.topological_sort(|_| true).unwrap_err();
+        assert_eq!(cycle.describe(&graph), "x -> y -> x");
+
+        let z = graph.id(&"z").unwrap();
+        assert_eq!(graph.topological_sort(|node| node == z).unwrap(), [z]);
+    }
+
+    #[test]
+    fn implicit_bfs() {
+        // Fewest doublings and increments from 1 to 10.
+        let found = bfs(1, |n| [n * 2, n + 1], |n| *n == 10);
+        assert_eq!(found, Some((10, 4)));
+        assert_eq!(bfs(1, |n| [(n * 2) % 8], |n| *n == 3), None);
+    }
+
+    #[test]
+    fn union_find() {
+        let mut sets = UnionFind::new(6);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+        assert_eq!(sets.find(0), sets.find(3));
+        assert_eq!(sets.size(2), 4);
+        assert_eq!(sets.sets(), 3);
+        let mut sizes = sets.set_sizes();
+        sizes.sort();
+        assert_eq!(sizes, [1, 1, 4]);
+    }
+}
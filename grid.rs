@@ -0,0 +1,394 @@
+use crate::parse::ParseError;
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+const OFFSETS_4: [(i64, i64); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const OFFSETS_8: [(i64, i64); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Dense rectangular grid stored in row-major order.
+///
+/// `Index` takes `(row, column)` and panics out of bounds; the signed
+/// accessors (`get`, `get_or`, `set`) treat anything outside the grid as
+/// missing so callers can probe neighbors without their own bounds checks.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    rows: usize,
+    columns: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(rows: usize, columns: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            rows,
+            columns,
+            cells: vec![fill; rows * columns],
+        }
+    }
+
+    /// Builds a grid from rows of equal length, or `None` if they are ragged.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let columns = rows.first().map_or(0, Vec::len);
+        let row_count = rows.len();
+        let mut cells = Vec::with_capacity(row_count * columns);
+        for values in rows {
+            if values.len() != columns {
+                return None;
+            }
+            cells.extend(values);
+        }
+
+        Some(Self {
+            rows: row_count,
+            columns,
+            cells,
+        })
+    }
+
+    /// Parses one row per line, converting each character with `cell`.
+    ///
+    /// Errors from `cell` and ragged rows are reported at their position in
+    /// `input`.
+    pub fn parse_with<E: Display>(
+        input: &str,
+        mut cell: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, ParseError> {
+        let mut rows = 0;
+        let mut columns = None;
+        let mut cells = Vec::new();
+        for line in input.lines() {
+            let mut length = 0;
+            for (index, char) in line.char_indices() {
+                let value = cell(char).map_err(|error| {
+                    let token = &line[index..index + char.len_utf8()];
+                    ParseError::at(input, token, error.to_string())
+                })?;
+                cells.push(value);
+                length += 1;
+            }
+
+            let expected = *columns.get_or_insert(length);
+            if length != expected {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    format!("row has {} columns, expected {}", length, expected),
+                ));
+            }
+            rows += 1;
+        }
+
+        Ok(Self {
+            rows,
+            columns: columns.unwrap_or(0),
+            cells,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn contains(&self, row: i64, column: i64) -> bool {
+        (0..self.rows as i64).contains(&row) && (0..self.columns as i64).contains(&column)
+    }
+
+    pub fn get(&self, row: i64, column: i64) -> Option<&T> {
+        if !self.contains(row, column) {
+            return None;
+        }
+
+        Some(&self.cells[row as usize * self.columns + column as usize])
+    }
+
+    pub fn get_mut(&mut self, row: i64, column: i64) -> Option<&mut T> {
+        if !self.contains(row, column) {
+            return None;
+        }
+
+        Some(&mut self.cells[row as usize * self.columns + column as usize])
+    }
+
+    /// Value at a position, or `default` outside the grid.
+    pub fn get_or(&self, row: i64, column: i64, default: T) -> T
+    where
+        T: Clone,
+    {
+        self.get(row, column).cloned().unwrap_or(default)
+    }
+
+    /// Sets a value, returning whether the position was inside the grid.
+    pub fn set(&mut self, row: i64, column: i64, value: T) -> bool {
+        match self.get_mut(row, column) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Every `(row, column)` in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let columns = self.columns;
+        (0..self.rows).flat_map(move |row| (0..columns).map(move |column| (row, column)))
+    }
+
+    /// Position of the first cell matching `predicate` in row-major order.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|index| (index / self.columns, index % self.columns))
+    }
+
+    /// In-bounds orthogonal neighbors of a position.
+    pub fn neighbors_4(
+        &self,
+        row: usize,
+        column: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(row, column, &OFFSETS_4)
+    }
+
+    /// In-bounds orthogonal and diagonal neighbors of a position.
+    pub fn neighbors_8(
+        &self,
+        row: usize,
+        column: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(row, column, &OFFSETS_8)
+    }
+
+    fn neighbors(
+        &self,
+        row: usize,
+        column: usize,
+        offsets: &'static [(i64, i64)],
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        offsets
+            .iter()
+            .filter_map(move |(row_offset, column_offset)| {
+                let neighbor_row = row as i64 + row_offset;
+                let neighbor_column = column as i64 + column_offset;
+                self.contains(neighbor_row, neighbor_column)
+                    .then_some((neighbor_row as usize, neighbor_column as usize))
+            })
+    }
+
+    /// Rotates a quarter turn counterclockwise.
+    pub fn rotate_left(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.columns, self.rows, |row, column| {
+            (column, self.columns - row - 1)
+        })
+    }
+
+    /// Rotates a quarter turn clockwise.
+    pub fn rotate_right(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.columns, self.rows, |row, column| {
+            (self.rows - column - 1, row)
+        })
+    }
+
+    /// Mirrors left to right.
+    pub fn mirror_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.rows, self.columns, |row, column| {
+            (row, self.columns - column - 1)
+        })
+    }
+
+    /// Mirrors top to bottom.
+    pub fn mirror_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.rows, self.columns, |row, column| {
+            (self.rows - row - 1, column)
+        })
+    }
+
+    // Builds a new grid where each target position takes the value at the
+    // source position returned by `source`.
+    fn remap(
+        &self,
+        rows: usize,
+        columns: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self
+    where
+        T: Clone,
+    {
+        let mut cells = Vec::with_capacity(rows * columns);
+        for row in 0..rows {
+            for column in 0..columns {
+                cells.push(self[source(row, column)].clone());
+            }
+        }
+
+        Self {
+            rows,
+            columns,
+            cells,
+        }
+    }
+}
+
+impl Grid<char> {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::parse_with(input, Ok::<char, std::convert::Infallible>)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, column): (usize, usize)) -> &T {
+        assert!(row < self.rows && column < self.columns);
+        &self.cells[row * self.columns + column]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut T {
+        assert!(row < self.rows && column < self.columns);
+        &mut self.cells[row * self.columns + column]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                write!(f, "{}", self[(row, column)])?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(rows: &[&str]) -> Grid<char> {
+        Grid::parse(&rows.join("\n")).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display_round_trip() {
+        let grid = grid(&["ab", "cd", "ef"]);
+        assert_eq!((grid.rows(), grid.columns()), (3, 2));
+        assert_eq!(grid[(1, 0)], 'c');
+        assert_eq!(grid.to_string(), "ab\ncd\nef\n");
+    }
+
+    #[test]
+    fn parse_rejects_ragged_rows() {
+        let error = Grid::parse("abc\nde\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn parse_with_reports_bad_cells() {
+        let error = Grid::parse_with("..\n.x", |cell| match cell {
+            '.' => Ok(false),
+            _ => Err("unexpected cell"),
+        })
+        .unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+
+    #[test]
+    fn signed_access_is_bounds_checked() {
+        let mut grid = grid(&["ab", "cd"]);
+        assert_eq!(grid.get(-1, 0), None);
+        assert_eq!(grid.get(1, 1), Some(&'d'));
+        assert_eq!(grid.get_or(0, 2, '.'), '.');
+        assert!(!grid.set(2, 0, 'x'));
+        assert!(grid.set(0, 1, 'x'));
+        assert_eq!(grid.to_string(), "ax\ncd\n");
+    }
+
+    #[test]
+    fn neighbors_stay_in_bounds() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(grid.neighbors_4(0, 0).collect::<Vec<_>>(), [(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbors_8(0, 0).count(), 3);
+        assert_eq!(grid.neighbors_4(1, 1).count(), 4);
+        assert_eq!(grid.neighbors_8(1, 1).count(), 8);
+    }
+
+    #[test]
+    fn find_returns_first_match() {
+        assert_eq!(grid(&["..", ".S"]).find(|cell| *cell == 'S'), Some((1, 1)));
+        assert_eq!(grid(&[".."]).find(|cell| *cell == 'S'), None);
+    }
+
+    #[test]
+    fn rotate_left_turns_counterclockwise() {
+        assert_eq!(
+            grid(&["abc", "def"]).rotate_left(),
+            grid(&["cf", "be", "ad"])
+        );
+    }
+
+    #[test]
+    fn rotate_right_undoes_rotate_left() {
+        let original = grid(&["##.", "#..", "###", "..#"]);
+        assert_eq!(original.rotate_left().rotate_right(), original);
+        assert_eq!(original.rotate_right(), grid(&[".###", ".#.#", "##.."]));
+    }
+
+    #[test]
+    fn rotate_left_four_times_is_identity() {
+        let original = grid(&["##.", "#..", "###"]);
+        let mut rotated = original.clone();
+        for _ in 0..4 {
+            rotated = rotated.rotate_left();
+        }
+        assert_eq!(rotated, original);
+    }
+
+    #[test]
+    fn mirrors() {
+        let original = grid(&["ab", "cd"]);
+        assert_eq!(original.mirror_horizontal(), grid(&["ba", "dc"]));
+        assert_eq!(original.mirror_vertical(), grid(&["cd", "ab"]));
+    }
+
+    #[test]
+    fn empty_grid() {
+        let empty = Grid::<char>::parse("").unwrap();
+        assert_eq!((empty.rows(), empty.columns()), (0, 0));
+        assert_eq!(empty.rotate_left(), empty);
+    }
+}
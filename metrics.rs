@@ -0,0 +1,146 @@
+use std::{
+    cell::RefCell,
+    fmt::{self, Display},
+    time::{Duration, Instant},
+};
+
+/// One recorded value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// Running total of `count` calls.
+    Counter(u64),
+    /// Last value passed to `gauge`.
+    Gauge(i64),
+    /// Time spent inside `time` and how often it was entered.
+    Timer { total: Duration, calls: u64 },
+}
+
+impl Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Metric::Counter(count) => write!(f, "{}", count),
+            Metric::Gauge(value) => write!(f, "{}", value),
+            Metric::Timer { total, calls } => write!(f, "{:.2?} over {} calls", total, calls),
+        }
+    }
+}
+
+/// Metrics recorded while solving one part, in the order they were first
+/// recorded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Metrics {
+    entries: Vec<(&'static str, Metric)>,
+}
+
+impl Metrics {
+    pub fn get(&self, name: &str) -> Option<Metric> {
+        self.entries
+            .iter()
+            .find(|(entry, _)| *entry == name)
+            .map(|(_, metric)| *metric)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, Metric)> + '_ {
+        self.entries.iter().copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn record(&mut self, name: &'static str, new: Metric, merge: impl FnOnce(&mut Metric)) {
+        match self.entries.iter_mut().find(|(entry, _)| *entry == name) {
+            Some((_, metric)) if std::mem::discriminant(metric) == std::mem::discriminant(&new) => {
+                merge(metric)
+            }
+            Some((_, metric)) => panic!("metric {} recorded as {:?} and {:?}", name, metric, new),
+            None => self.entries.push((name, new)),
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Metrics> = RefCell::default();
+}
+
+/// Adds `amount` to the named counter. Record coarse totals (say, once per
+/// search) rather than every step of a hot loop.
+pub fn count(name: &'static str, amount: u64) {
+    CURRENT.with_borrow_mut(|metrics| {
+        metrics.record(name, Metric::Counter(amount), |metric| {
+            if let Metric::Counter(count) = metric {
+                *count += amount;
+            }
+        })
+    });
+}
+
+/// Sets the named gauge, replacing any earlier value.
+pub fn gauge(name: &'static str, value: i64) {
+    CURRENT.with_borrow_mut(|metrics| {
+        metrics.record(name, Metric::Gauge(value), |metric| {
+            *metric = Metric::Gauge(value)
+        })
+    });
+}
+
+/// Runs `work`, adding its duration to the named timer.
+pub fn time<R>(name: &'static str, work: impl FnOnce() -> R) -> R {
+    let start = Instant::now();
+    let result = work();
+    let elapsed = start.elapsed();
+    CURRENT.with_borrow_mut(|metrics| {
+        let new = Metric::Timer {
+            total: elapsed,
+            calls: 1,
+        };
+        metrics.record(name, new, |metric| {
+            if let Metric::Timer { total, calls } = metric {
+                *total += elapsed;
+                *calls += 1;
+            }
+        })
+    });
+
+    result
+}
+
+/// Returns everything recorded on this thread since the last call and starts
+/// afresh. `solve_part` calls this around each part, so solvers only record.
+pub fn take() -> Metrics {
+    CURRENT.take()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_in_first_use_order() {
+        take();
+        count("placements", 3);
+        gauge("depth", 4);
+        count("placements", 2);
+        gauge("depth", 1);
+        assert_eq!(time("search", || 7), 7);
+        time("search", || ());
+
+        let metrics = take();
+        let names = metrics.iter().map(|(name, _)| name).collect::<Vec<_>>();
+        assert_eq!(names, ["placements", "depth", "search"]);
+        assert_eq!(metrics.get("placements"), Some(Metric::Counter(5)));
+        assert_eq!(metrics.get("depth"), Some(Metric::Gauge(1)));
+        assert!(matches!(
+            metrics.get("search"),
+            Some(Metric::Timer { calls: 2, .. })
+        ));
+        assert!(take().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "metric mixed recorded as")]
+    fn rejects_mixed_kinds() {
+        count("mixed", 1);
+        gauge("mixed", 1);
+    }
+}
@@ -0,0 +1,173 @@
+use crate::error::{self, Error};
+use std::{
+    fmt::{Debug, Display},
+    str::FromStr,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// Integer type that answers are accumulated in. Arithmetic is always
+/// checked: overflow is reported as `Error::Overflow` instead of wrapping (in
+/// release builds) or panicking (in debug builds).
+pub trait Integer: Copy + Ord + Debug + Display + Send + Sync + 'static {
+    const ZERO: Self;
+    const ONE: Self;
+    /// Name of the type, for error messages.
+    const NAME: &'static str;
+
+    fn try_add(self, other: Self) -> error::Result<Self>;
+
+    fn try_sub(self, other: Self) -> error::Result<Self>;
+
+    fn try_mul(self, other: Self) -> error::Result<Self>;
+
+    /// Converts a signed value, failing if it does not fit.
+    fn try_from_i64(value: i64) -> error::Result<Self>;
+
+    /// Converts an unsigned value (such as a count), failing if it does not
+    /// fit.
+    fn try_from_u64(value: u64) -> error::Result<Self>;
+
+    /// Sum of `values`, failing on the first overflow.
+    fn try_sum(values: impl IntoIterator<Item = Self>) -> error::Result<Self> {
+        values
+            .into_iter()
+            .try_fold(Self::ZERO, |total, value| total.try_add(value))
+    }
+
+    /// Product of `values`, failing on the first overflow.
+    fn try_product(values: impl IntoIterator<Item = Self>) -> error::Result<Self> {
+        values
+            .into_iter()
+            .try_fold(Self::ONE, |total, value| total.try_mul(value))
+    }
+}
+
+fn overflow(name: &str, operation: &str, a: impl Display, b: impl Display) -> Error {
+    Error::Overflow(format!(
+        "{} {} {} does not fit in {}",
+        a, operation, b, name
+    ))
+}
+
+fn conversion(name: &str, value: impl Display) -> Error {
+    Error::Overflow(format!("{} does not fit in {}", value, name))
+}
+
+macro_rules! impl_integer {
+    ($($type:ty),*) => {$(
+        impl Integer for $type {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const NAME: &'static str = stringify!($type);
+
+            fn try_add(self, other: Self) -> error::Result<Self> {
+                self.checked_add(other)
+                    .ok_or_else(|| overflow(Self::NAME, "+", self, other))
+            }
+
+            fn try_sub(self, other: Self) -> error::Result<Self> {
+                self.checked_sub(other)
+                    .ok_or_else(|| overflow(Self::NAME, "-", self, other))
+            }
+
+            fn try_mul(self, other: Self) -> error::Result<Self> {
+                self.checked_mul(other)
+                    .ok_or_else(|| overflow(Self::NAME, "*", self, other))
+            }
+
+            fn try_from_i64(value: i64) -> error::Result<Self> {
+                value.try_into().map_err(|_| conversion(Self::NAME, value))
+            }
+
+            fn try_from_u64(value: u64) -> error::Result<Self> {
+                value.try_into().map_err(|_| conversion(Self::NAME, value))
+            }
+        }
+    )*};
+}
+
+impl_integer!(i64, u64, i128, u128, usize);
+
+/// Width of the integers answers are accumulated in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// 64 bits, enough for every real input.
+    #[default]
+    Checked,
+    /// 128 bits, for generated inputs whose answers outgrow 64.
+    Wide,
+}
+
+impl FromStr for Mode {
+    type Err = Error;
+
+    fn from_str(mode: &str) -> error::Result<Self> {
+        match mode {
+            "checked" => Ok(Mode::Checked),
+            "wide" => Ok(Mode::Wide),
+            _ => Err(Error::Usage(format!("invalid arithmetic mode: {}", mode))),
+        }
+    }
+}
+
+static WIDE: AtomicBool = AtomicBool::new(false);
+
+/// Sets the mode `in_mode` picks from now on, on every thread.
+pub fn configure(mode: Mode) {
+    WIDE.store(mode == Mode::Wide, Ordering::Relaxed);
+}
+
+pub fn mode() -> Mode {
+    match WIDE.load(Ordering::Relaxed) {
+        true => Mode::Wide,
+        false => Mode::Checked,
+    }
+}
+
+/// Runs the solver instantiated for the configured mode: `checked` with
+/// `i64` (or another 64-bit type) and `wide` with its 128-bit counterpart.
+pub fn in_mode<A: Display, B: Display>(
+    checked: impl FnOnce() -> error::Result<A>,
+    wide: impl FnOnce() -> error::Result<B>,
+) -> error::Result<String> {
+    match mode() {
+        Mode::Checked => checked().map(|answer| answer.to_string()),
+        Mode::Wide => wide().map(|answer| answer.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_overflow() {
+        assert_eq!(i64::MAX.try_sub(1).unwrap(), i64::MAX - 1);
+        assert!(matches!(i64::MAX.try_add(1), Err(Error::Overflow(_))));
+        assert!(matches!(0u64.try_sub(1), Err(Error::Overflow(_))));
+        let error = (1i64 << 62).try_mul(4).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "arithmetic overflow: 4611686018427387904 * 4 does not fit in i64"
+        );
+        assert_eq!((1i128 << 62).try_mul(4).unwrap(), 1 << 64);
+    }
+
+    #[test]
+    fn converts_and_folds() {
+        assert_eq!(i64::try_from_u64(7).unwrap(), 7);
+        assert!(matches!(u64::try_from_i64(-1), Err(Error::Overflow(_))));
+        assert!(i64::try_from_u64(u64::MAX).is_err());
+        assert_eq!(i64::try_sum([1, 2, 3]).unwrap(), 6);
+        assert_eq!(i64::try_product([]).unwrap(), 1);
+        assert!(i64::try_product([1 << 32, 1 << 32]).is_err());
+        assert_eq!(i128::try_product([1 << 32, 1 << 32]).unwrap(), 1 << 64);
+    }
+
+    #[test]
+    fn parses_modes() {
+        assert_eq!("checked".parse::<Mode>().unwrap(), Mode::Checked);
+        assert_eq!("wide".parse::<Mode>().unwrap(), Mode::Wide);
+        assert!("wrapping".parse::<Mode>().is_err());
+    }
+}
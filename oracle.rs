@@ -0,0 +1,221 @@
+use crate::generate::Rng;
+use std::{env, fmt::Debug};
+
+/// Environment variable overriding how many random cases `assert_agrees`
+/// tries, e.g. for a longer run before trusting a new shortcut.
+pub const CASES_VAR: &str = "AOC_ORACLE_CASES";
+
+pub const DEFAULT_CASES: usize = 200;
+
+/// Upper bound on accepted shrinking steps, in case a shrinker keeps
+/// proposing failing inputs that are not actually smaller.
+const MAX_SHRINKS: usize = 10_000;
+
+/// Input on which a fast solver and its reference implementation disagree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mismatch<T, R> {
+    /// Seed of the random case that first failed.
+    pub seed: u64,
+    /// The failing input as generated.
+    pub original: T,
+    /// The smallest failing input reached by shrinking `original`.
+    pub input: T,
+    pub fast: R,
+    pub oracle: R,
+    /// Number of shrinking steps taken.
+    pub shrinks: usize,
+}
+
+/// Runs `fast` and `oracle` on `cases` random inputs (case `n` is generated
+/// from seed `n`) and returns the first disagreement, shrunk as far as
+/// `shrink` allows.
+///
+/// `shrink` proposes strictly smaller variants of an input, most aggressive
+/// first; the first variant that still fails replaces the input until none
+/// does.
+pub fn find_mismatch<T, R>(
+    cases: usize,
+    generate: impl Fn(&mut Rng) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    fast: impl Fn(&T) -> R,
+    oracle: impl Fn(&T) -> R,
+) -> Option<Mismatch<T, R>>
+where
+    T: Clone,
+    R: PartialEq,
+{
+    let disagreement = |input: &T| {
+        let (fast, oracle) = (fast(input), oracle(input));
+        (fast != oracle).then_some((fast, oracle))
+    };
+
+    for seed in 0..cases as u64 {
+        let original = generate(&mut Rng::new(seed));
+        let Some(mut results) = disagreement(&original) else {
+            continue;
+        };
+
+        let mut input = original.clone();
+        let mut shrinks = 0;
+        'shrinking: while shrinks < MAX_SHRINKS {
+            for candidate in shrink(&input) {
+                if let Some(candidate_results) = disagreement(&candidate) {
+                    input = candidate;
+                    results = candidate_results;
+                    shrinks += 1;
+                    continue 'shrinking;
+                }
+            }
+            break;
+        }
+
+        let (fast, oracle) = results;
+        return Some(Mismatch {
+            seed,
+            original,
+            input,
+            fast,
+            oracle,
+            shrinks,
+        });
+    }
+
+    None
+}
+
+/// Panics with the shrunk counterexample if `fast` and `oracle` disagree on
+/// any random input. The number of cases is `DEFAULT_CASES` unless
+/// `AOC_ORACLE_CASES` says otherwise.
+pub fn assert_agrees<T, R>(
+    generate: impl Fn(&mut Rng) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    fast: impl Fn(&T) -> R,
+    oracle: impl Fn(&T) -> R,
+) where
+    T: Clone + Debug,
+    R: PartialEq + Debug,
+{
+    let cases = env::var(CASES_VAR)
+        .ok()
+        .and_then(|cases| cases.parse().ok())
+        .unwrap_or(DEFAULT_CASES);
+    if let Some(mismatch) = find_mismatch(cases, generate, shrink, fast, oracle) {
+        panic!(
+            "fast solver disagrees with oracle (seed {}, shrunk in {} steps)\n\
+             input:    {:?}\n\
+             fast:     {:?}\n\
+             oracle:   {:?}\n\
+             original: {:?}",
+            mismatch.seed,
+            mismatch.shrinks,
+            mismatch.input,
+            mismatch.fast,
+            mismatch.oracle,
+            mismatch.original
+        );
+    }
+}
+
+/// Values between `target` and `value` (excluding `value`), closest to
+/// `target` first: `target` itself, then halving the distance each time.
+pub fn shrink_integer(value: i64, target: i64) -> Vec<i64> {
+    let mut candidates = Vec::new();
+    let mut distance = value - target;
+    while distance != 0 {
+        candidates.push(value - distance);
+        distance /= 2;
+    }
+
+    candidates
+}
+
+/// Smaller versions of `items`: first with runs of items removed (halves,
+/// then quarters, down to single items), then with one item replaced by each
+/// of its `shrink_item` candidates.
+pub fn shrink_vec<T: Clone>(items: &[T], shrink_item: impl Fn(&T) -> Vec<T>) -> Vec<Vec<T>> {
+    let mut candidates = Vec::new();
+    let mut chunk = items.len();
+    while chunk > 0 {
+        for start in (0..items.len()).step_by(chunk) {
+            let end = (start + chunk).min(items.len());
+            candidates.push([&items[..start], &items[end..]].concat());
+        }
+        chunk /= 2;
+    }
+    for (index, item) in items.iter().enumerate() {
+        for smaller in shrink_item(item) {
+            let mut candidate = items.to_vec();
+            candidate[index] = smaller;
+            candidates.push(candidate);
+        }
+    }
+
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agreeing_solvers_pass() {
+        let mismatch = find_mismatch(
+            50,
+            |rng| rng.range(0..=1000),
+            |value| shrink_integer(*value, 0),
+            |value| value * 2,
+            |value| value + value,
+        );
+        assert_eq!(mismatch, None);
+    }
+
+    #[test]
+    fn shrinks_to_smallest_counterexample() {
+        // Wrong for every value of at least 100; the smallest is 100 itself.
+        let mismatch = find_mismatch(
+            50,
+            |rng| rng.range(0..=1000),
+            |value| shrink_integer(*value, 0),
+            |value| if *value >= 100 { 0 } else { *value },
+            |value| *value,
+        )
+        .unwrap();
+        assert_eq!(
+            (mismatch.input, mismatch.fast, mismatch.oracle),
+            (100, 0, 100)
+        );
+        assert!(mismatch.original >= 100);
+    }
+
+    #[test]
+    fn shrinks_vectors_by_removing_and_shrinking_items() {
+        // Disagrees whenever some item is at least 10.
+        let mismatch = find_mismatch(
+            50,
+            |rng| (0..8).map(|_| rng.range(0..=20)).collect::<Vec<i64>>(),
+            |items| shrink_vec(items, |item| shrink_integer(*item, 0)),
+            |items| items.iter().any(|item| *item >= 10),
+            |_| false,
+        )
+        .unwrap();
+        assert_eq!(mismatch.input, [10]);
+    }
+
+    #[test]
+    fn integer_candidates_approach_target() {
+        assert_eq!(shrink_integer(10, 0), [0, 5, 8, 9]);
+        assert_eq!(shrink_integer(-3, 0), [0, -2]);
+        assert_eq!(shrink_integer(4, 4), []);
+    }
+
+    #[test]
+    #[should_panic(expected = "input:    100")]
+    fn assert_agrees_reports_shrunk_input() {
+        assert_agrees(
+            |rng| rng.range(0..=1000),
+            |value| shrink_integer(*value, 0),
+            |value| *value >= 100,
+            |_| false,
+        );
+    }
+}
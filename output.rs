@@ -0,0 +1,220 @@
+use crate::{
+    Answers, InputSource,
+    error::{self, Error},
+    metrics::{Metric, Metrics},
+};
+use std::{fmt::Write, str::FromStr};
+
+/// How answers are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Human-readable lines.
+    Text,
+    /// A JSON array with one object per answer (see `json_records`).
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(format: &str) -> error::Result<Self> {
+        match format {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(Error::Usage(format!("invalid format: {}", format))),
+        }
+    }
+}
+
+/// JSON objects describing one day's run: one per answer, or a single object
+/// with `"status": "error"` and a null part and answer if the day failed.
+///
+/// Every object has `day`, `part`, `answer` (a string, since answers need not
+/// fit in a double, or null if the part ran out of budget), `input`,
+/// `elapsed_ns` and `status` (`solved`, `unknown` or `error`); failed days add
+/// `error`. With `stats`, answers also carry a
+/// `stats` object of their metrics (see `json_metrics`).
+pub fn json_records(
+    day: u8,
+    input: &InputSource,
+    result: &error::Result<Answers>,
+    stats: bool,
+) -> Vec<String> {
+    let day = day.to_string();
+    let input = json_string(&input.to_string());
+    match result {
+        Ok(answers) => answers
+            .iter()
+            .map(|answer| {
+                let mut fields = vec![
+                    ("day", day.clone()),
+                    ("part", answer.part.to_string()),
+                    (
+                        "answer",
+                        answer
+                            .value
+                            .as_deref()
+                            .map_or(NULL.to_string(), json_string),
+                    ),
+                    ("input", input.clone()),
+                    ("elapsed_ns", answer.elapsed.as_nanos().to_string()),
+                    ("status", json_string(&answer.status.to_string())),
+                ];
+                if stats {
+                    fields.push(("stats", json_metrics(&answer.metrics)));
+                }
+                json_object(&fields)
+            })
+            .collect(),
+        Err(error) => vec![json_object(&[
+            ("day", day),
+            ("part", NULL.to_string()),
+            ("answer", NULL.to_string()),
+            ("input", input),
+            ("elapsed_ns", NULL.to_string()),
+            ("status", json_string("error")),
+            ("error", json_string(&error.to_string())),
+        ])],
+    }
+}
+
+const NULL: &str = "null";
+
+/// Metrics as a JSON object keyed by name. Counters and gauges are numbers;
+/// timers are `{"total_ns": ..., "calls": ...}`.
+pub fn json_metrics(metrics: &Metrics) -> String {
+    let fields = metrics
+        .iter()
+        .map(|(name, metric)| {
+            let value = match metric {
+                Metric::Counter(count) => count.to_string(),
+                Metric::Gauge(value) => value.to_string(),
+                Metric::Timer { total, calls } => json_object(&[
+                    ("total_ns", total.as_nanos().to_string()),
+                    ("calls", calls.to_string()),
+                ]),
+            };
+            (name, value)
+        })
+        .collect::<Vec<(&str, String)>>();
+    json_object(&fields)
+}
+
+/// Metrics as an indented table under `heading`, for `--stats`.
+pub fn stats_text(heading: &str, metrics: &Metrics) -> String {
+    if metrics.is_empty() {
+        return format!("{} stats: none recorded\n", heading);
+    }
+
+    let width = metrics
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    let mut text = format!("{} stats:\n", heading);
+    for (name, metric) in metrics.iter() {
+        let _ = writeln!(text, "  {:<width$}  {}", name, metric, width = width);
+    }
+
+    text
+}
+
+/// Renders already-rendered JSON values as an object, keeping field order.
+pub fn json_object(fields: &[(&str, String)]) -> String {
+    let fields = fields
+        .iter()
+        .map(|(key, value)| format!("{}: {}", json_string(key), value))
+        .collect::<Vec<String>>();
+    format!("{{{}}}", fields.join(", "))
+}
+
+/// Joins already-rendered JSON values into an array, one element per line.
+pub fn json_array(values: &[String]) -> String {
+    if values.is_empty() {
+        return "[]".to_string();
+    }
+
+    format!("[\n  {}\n]", values.join(",\n  "))
+}
+
+/// Quotes and escapes a string as a JSON string literal.
+pub fn json_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for char in value.chars() {
+        match char {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            char if char.is_control() => {
+                let _ = write!(quoted, "\\u{:04x}", char as u32);
+            }
+            char => quoted.push(char),
+        }
+    }
+    quoted.push('"');
+
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Answer, Status};
+    use std::{path::PathBuf, time::Duration};
+
+    #[test]
+    fn escapes_strings() {
+        assert_eq!(json_string("a\"b\\c\nd\u{1}"), "\"a\\\"b\\\\c\\nd\\u0001\"");
+    }
+
+    #[test]
+    fn renders_answers_and_errors() {
+        let input = InputSource::Path(PathBuf::from("12.txt"));
+        let answers = vec![Answer {
+            part: 1,
+            value: Some("2".to_string()),
+            status: Status::Unknown,
+            elapsed: Duration::from_nanos(1500),
+            metrics: Metrics::default(),
+        }];
+        assert_eq!(
+            json_records(12, &input, &Ok(answers), false),
+            [
+                "{\"day\": 12, \"part\": 1, \"answer\": \"2\", \"input\": \"12.txt\", \"elapsed_ns\": 1500, \"status\": \"unknown\"}"
+            ]
+        );
+
+        let error = Err(Error::Infeasible("no path".to_string()));
+        let records = json_records(11, &InputSource::Stdin, &error, true);
+        assert_eq!(
+            records,
+            [
+                "{\"day\": 11, \"part\": null, \"answer\": null, \"input\": \"<stdin>\", \"elapsed_ns\": null, \"status\": \"error\", \"error\": \"no solution: no path\"}"
+            ]
+        );
+        assert_eq!(json_array(&records[..0]), "[]");
+    }
+
+    #[test]
+    fn renders_metrics() {
+        crate::metrics::take();
+        crate::metrics::count("placements", 12);
+        crate::metrics::gauge("depth", -1);
+        let recorded = crate::metrics::take();
+        assert_eq!(
+            json_metrics(&recorded),
+            "{\"placements\": 12, \"depth\": -1}"
+        );
+        assert_eq!(
+            stats_text("part 1", &recorded),
+            "part 1 stats:\n  placements  12\n  depth       -1\n"
+        );
+        assert_eq!(
+            stats_text("part 2", &Metrics::default()),
+            "part 2 stats: none recorded\n"
+        );
+    }
+}
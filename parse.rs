@@ -0,0 +1,211 @@
+use std::fmt::{self, Display};
+
+/// Parse failure pointing at the offending text of the input.
+///
+/// Rendered like a compiler diagnostic, with a caret under the bad token:
+///
+/// ```text
+/// invalid number: invalid digit found in string
+///  --> 1.txt:3:2
+///   |
+/// 3 | L1x
+///   |  ^^
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    /// Name of the input, attached once it is known (see `in_file`).
+    pub file: Option<String>,
+    /// One-based line number.
+    pub line: usize,
+    /// One-based column, in characters.
+    pub column: usize,
+    /// Width of the offending token, in characters (at least one).
+    pub length: usize,
+    /// Full text of the offending line.
+    pub snippet: String,
+}
+
+impl ParseError {
+    /// Error at `token`, which must be a subslice of `source` (as produced by
+    /// `lines`, `split` and friends); any other token is reported at the
+    /// start of `source`.
+    pub fn at(source: &str, token: &str, message: impl Into<String>) -> Self {
+        let source_start = source.as_ptr() as usize;
+        let token_start = token.as_ptr() as usize;
+        let within =
+            token_start >= source_start && token_start + token.len() <= source_start + source.len();
+        let (offset, length) = if within {
+            (token_start - source_start, token.chars().count())
+        } else {
+            (0, 1)
+        };
+        Self::at_offset(source, offset, length, message)
+    }
+
+    /// Error at the end of `source`, for input that stops too early.
+    pub fn at_end(source: &str, message: impl Into<String>) -> Self {
+        Self::at_offset(source, source.trim_end().len(), 1, message)
+    }
+
+    /// Error at a byte offset into `source` spanning `length` characters.
+    pub fn at_offset(
+        source: &str,
+        offset: usize,
+        length: usize,
+        message: impl Into<String>,
+    ) -> Self {
+        let offset = offset.min(source.len());
+        let line_start = source[..offset].rfind('\n').map_or(0, |index| index + 1);
+        let line_end = source[offset..]
+            .find('\n')
+            .map_or(source.len(), |index| offset + index);
+        let snippet = source[line_start..line_end].trim_end_matches('\r');
+        let column = source[line_start..offset].chars().count() + 1;
+
+        // Keep the caret on the offending line.
+        let remaining = snippet.chars().count().saturating_sub(column - 1);
+        Self {
+            message: message.into(),
+            file: None,
+            line: source[..offset].matches('\n').count() + 1,
+            column,
+            length: length.min(remaining).max(1),
+            snippet: snippet.to_string(),
+        }
+    }
+
+    /// Re-anchors an error from parsing `fragment`, a subslice of `source`,
+    /// so that it points into `source` instead.
+    pub fn within(self, source: &str, fragment: &str) -> Self {
+        let line_start = fragment
+            .split_inclusive('\n')
+            .take(self.line - 1)
+            .map(str::len)
+            .sum::<usize>();
+        let column_offset = fragment[line_start..]
+            .char_indices()
+            .nth(self.column - 1)
+            .map_or(fragment.len() - line_start, |(index, _)| index);
+        let token = &fragment[line_start + column_offset..];
+        let error = Self::at(source, token, self.message);
+        Self {
+            length: self.length,
+            file: self.file,
+            ..error
+        }
+    }
+
+    pub fn in_file(mut self, file: impl Into<String>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{}", self.message)?;
+        writeln!(
+            f,
+            "{}--> {}:{}:{}",
+            gutter,
+            self.file.as_deref().unwrap_or("<input>"),
+            self.line,
+            self.column
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(self.length)
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Converts failures while parsing a token into a `ParseError` at that token,
+/// much like `Option::ok_or` and `Result::map_err` with a position attached.
+pub trait ParseContext<T> {
+    fn context_at(self, source: &str, token: &str, message: &str) -> Result<T, ParseError>;
+}
+
+impl<T, E: Display> ParseContext<T> for Result<T, E> {
+    fn context_at(self, source: &str, token: &str, message: &str) -> Result<T, ParseError> {
+        self.map_err(|error| ParseError::at(source, token, format!("{}: {}", message, error)))
+    }
+}
+
+impl<T> ParseContext<T> for Option<T> {
+    fn context_at(self, source: &str, token: &str, message: &str) -> Result<T, ParseError> {
+        self.ok_or_else(|| ParseError::at(source, token, message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "R10\nL1x\nR5\n";
+
+    #[test]
+    fn locates_token_by_position() {
+        let token = &SOURCE.lines().nth(1).unwrap()[1..];
+        let error = ParseError::at(SOURCE, token, "invalid number");
+        assert_eq!((error.line, error.column, error.length), (2, 2, 2));
+        assert_eq!(error.snippet, "L1x");
+    }
+
+    #[test]
+    fn renders_caret_under_token() {
+        let token = &SOURCE.lines().nth(1).unwrap()[1..];
+        let error = ParseError::at(SOURCE, token, "invalid number").in_file("1.txt");
+        assert_eq!(
+            error.to_string(),
+            "invalid number\n --> 1.txt:2:2\n  |\n2 | L1x\n  |  ^^"
+        );
+    }
+
+    #[test]
+    fn foreign_token_points_at_start() {
+        let error = ParseError::at(SOURCE, "L1x", "invalid number");
+        assert_eq!((error.line, error.column, error.length), (1, 1, 1));
+    }
+
+    #[test]
+    fn end_of_input() {
+        let error = ParseError::at_end("3-5\n10-14\n\n", "missing ingredients");
+        assert_eq!((error.line, error.column), (2, 6));
+        assert_eq!(error.snippet, "10-14");
+    }
+
+    #[test]
+    fn multiline_token_is_clipped_to_its_line() {
+        let error = ParseError::at(SOURCE, &SOURCE[4..], "bad block");
+        assert_eq!((error.line, error.column, error.length), (2, 1, 3));
+    }
+
+    #[test]
+    fn within_moves_error_into_source() {
+        let fragment = &SOURCE[4..];
+        let token = &fragment.lines().nth(1).unwrap()[1..];
+        let error = ParseError::at(fragment, token, "invalid number").within(SOURCE, fragment);
+        assert_eq!((error.line, error.column, error.length), (3, 2, 1));
+        assert_eq!(error.snippet, "R5");
+    }
+
+    #[test]
+    fn context_at_wraps_errors() {
+        let error = "x"
+            .parse::<i32>()
+            .context_at(SOURCE, SOURCE, "invalid number");
+        assert_eq!(
+            error.unwrap_err().message,
+            "invalid number: invalid digit found in string"
+        );
+    }
+}
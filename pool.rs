@@ -0,0 +1,94 @@
+use std::{
+    panic,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+/// Applies `work` to every item on up to `jobs` threads and returns the
+/// results in item order.
+///
+/// Threads pull the next unclaimed item as soon as they finish one, so a slow
+/// item only occupies its own thread. A panic in `work` is re-raised on the
+/// calling thread once every thread has stopped.
+pub fn map<T, R>(items: &[T], jobs: usize, work: impl Fn(&T) -> R + Sync) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    let jobs = jobs.clamp(1, items.len().max(1));
+    let next = AtomicUsize::new(0);
+    let mut results = (0..items.len()).map(|_| None).collect::<Vec<Option<R>>>();
+    thread::scope(|scope| {
+        let workers = (0..jobs)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(index) else {
+                            break;
+                        };
+                        done.push((index, work(item)));
+                    }
+                    done
+                })
+            })
+            .collect::<Vec<_>>();
+        for worker in workers {
+            match worker.join() {
+                Ok(done) => {
+                    for (index, result) in done {
+                        results[index] = Some(result);
+                    }
+                }
+                Err(payload) => panic::resume_unwind(payload),
+            }
+        }
+    });
+
+    results
+        .into_iter()
+        .map(|result| result.expect("every item is claimed by exactly one worker"))
+        .collect()
+}
+
+/// Number of threads to use when the caller does not choose.
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, usize::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn preserves_item_order() {
+        let items = (0..50).collect::<Vec<u64>>();
+        let squares = map(&items, 4, |item| {
+            // Finish out of order.
+            thread::sleep(Duration::from_micros(50 - item));
+            item * item
+        });
+        assert_eq!(
+            squares,
+            items.iter().map(|item| item * item).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn handles_more_jobs_than_items() {
+        assert_eq!(map(&[1, 2], 16, |item| item + 1), [2, 3]);
+        assert_eq!(map(&[] as &[u8], 4, |item| *item), []);
+    }
+
+    #[test]
+    #[should_panic(expected = "boom")]
+    fn propagates_panics() {
+        map(&[1, 2, 3], 2, |item| {
+            if *item == 2 {
+                panic!("boom");
+            }
+        });
+    }
+}
@@ -0,0 +1,251 @@
+use crate::parse::{ParseContext, ParseError};
+use std::{
+    cmp::{max, min},
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// Inclusive range of integers with `start <= end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Range {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Range {
+    /// Range from `start` to `end`, or `None` if `start` is after `end`.
+    pub fn new(start: i64, end: i64) -> Option<Self> {
+        (start <= end).then_some(Self { start, end })
+    }
+
+    /// Number of integers in the range.
+    pub fn length(&self) -> i64 {
+        self.end - self.start + 1
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    pub fn intersection(&self, other: &Range) -> Option<Range> {
+        let start = max(self.start, other.start);
+        let end = min(self.end, other.end);
+        (start <= end).then_some(Range { start, end })
+    }
+}
+
+/// Parses `start-end`, e.g. `11-22`. Errors point into the parsed string;
+/// use `ParseError::within` to locate them in a larger input.
+impl FromStr for Range {
+    type Err = ParseError;
+
+    fn from_str(range: &str) -> Result<Self, ParseError> {
+        // Skip the first character so a negative start is not split on.
+        let separator = range
+            .char_indices()
+            .skip(1)
+            .find(|(_, char)| *char == '-')
+            .map(|(index, _)| index)
+            .context_at(range, range, "expected start-end")?;
+        let (start_str, end_str) = (&range[..separator], &range[separator + 1..]);
+        let start = start_str
+            .parse::<i64>()
+            .context_at(range, start_str, "invalid start")?;
+        let end = end_str
+            .parse::<i64>()
+            .context_at(range, end_str, "invalid end")?;
+        Range::new(start, end).context_at(range, range, "start is after end")
+    }
+}
+
+impl Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// Set of integers stored as sorted, disjoint and non-adjacent ranges.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeSet {
+    ranges: Vec<Range>,
+}
+
+impl RangeSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a range, merging it with any ranges it overlaps or touches.
+    pub fn insert(&mut self, range: Range) {
+        // Ranges entirely before the new one (with a gap) are untouched.
+        let first = self
+            .ranges
+            .partition_point(|existing| existing.end < range.start.saturating_sub(1));
+
+        // Absorb every range that overlaps or is adjacent to the new one.
+        let mut merged = range;
+        let mut last = first;
+        while let Some(existing) = self.ranges.get(last)
+            && existing.start <= merged.end.saturating_add(1)
+        {
+            merged.start = min(merged.start, existing.start);
+            merged.end = max(merged.end, existing.end);
+            last += 1;
+        }
+
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let index = self.ranges.partition_point(|range| range.end < value);
+        self.ranges
+            .get(index)
+            .is_some_and(|range| range.contains(value))
+    }
+
+    pub fn union(&self, other: &RangeSet) -> RangeSet {
+        let mut union = self.clone();
+        for range in &other.ranges {
+            union.insert(*range);
+        }
+
+        union
+    }
+
+    pub fn intersection(&self, other: &RangeSet) -> RangeSet {
+        let mut intersection = RangeSet::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            if let Some(range) = self.ranges[i].intersection(&other.ranges[j]) {
+                intersection.ranges.push(range);
+            }
+
+            // Advance whichever range finishes first.
+            if self.ranges[i].end < other.ranges[j].end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        intersection
+    }
+
+    pub fn difference(&self, other: &RangeSet) -> RangeSet {
+        let mut difference = RangeSet::new();
+        for range in &self.ranges {
+            // Start of the part of `range` not yet covered, if any remains.
+            let mut start = Some(range.start);
+            let first = other
+                .ranges
+                .partition_point(|removed| removed.end < range.start);
+            for removed in &other.ranges[first..] {
+                let Some(current) = start else {
+                    break;
+                };
+                if removed.start > range.end {
+                    break;
+                }
+
+                if removed.start > current {
+                    difference.ranges.push(Range {
+                        start: current,
+                        end: removed.start - 1,
+                    });
+                }
+                start = (removed.end < range.end).then(|| removed.end + 1);
+            }
+
+            if let Some(start) = start {
+                difference.ranges.push(Range {
+                    start,
+                    end: range.end,
+                });
+            }
+        }
+
+        difference
+    }
+
+    /// Number of integers across every range.
+    pub fn total_length(&self) -> i64 {
+        self.ranges.iter().map(Range::length).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn ranges(&self) -> &[Range] {
+        &self.ranges
+    }
+}
+
+impl FromIterator<Range> for RangeSet {
+    fn from_iter<I: IntoIterator<Item = Range>>(ranges: I) -> Self {
+        let mut set = RangeSet::new();
+        for range in ranges {
+            set.insert(range);
+        }
+
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: i64, end: i64) -> Range {
+        Range::new(start, end).unwrap()
+    }
+
+    fn set(ranges: &[(i64, i64)]) -> RangeSet {
+        ranges
+            .iter()
+            .map(|(start, end)| range(*start, *end))
+            .collect()
+    }
+
+    #[test]
+    fn parse_range() {
+        assert_eq!("11-22".parse::<Range>().unwrap(), range(11, 22));
+        assert_eq!("-5-3".parse::<Range>().unwrap(), range(-5, 3));
+        assert_eq!(
+            "22-11".parse::<Range>().unwrap_err().message,
+            "start is after end"
+        );
+        assert_eq!("1-x".parse::<Range>().unwrap_err().column, 3);
+        assert!("11".parse::<Range>().is_err());
+        assert!("a-b".parse::<Range>().is_err());
+    }
+
+    #[test]
+    fn insert_merges_overlapping_and_adjacent() {
+        let ranges = set(&[(10, 14), (3, 5), (16, 20), (12, 18)]);
+        assert_eq!(ranges.ranges(), [range(3, 5), range(10, 20)]);
+        assert_eq!(set(&[(1, 3), (4, 5)]).ranges(), [range(1, 5)]);
+        assert_eq!(set(&[(1, 10), (3, 4)]).ranges(), [range(1, 10)]);
+    }
+
+    #[test]
+    fn contains_and_total_length() {
+        let ranges = set(&[(3, 5), (10, 14), (16, 20), (12, 18)]);
+        assert_eq!(ranges.total_length(), 14);
+        assert!(ranges.contains(5));
+        assert!(!ranges.contains(8));
+        assert!(ranges.contains(17));
+        assert!(!ranges.contains(32));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[(1, 10), (20, 30)]);
+        let b = set(&[(5, 25)]);
+        assert_eq!(a.union(&b), set(&[(1, 30)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25)]));
+        assert_eq!(a.difference(&b), set(&[(1, 4), (26, 30)]));
+        assert_eq!(b.difference(&a), set(&[(11, 19)]));
+        assert!(a.difference(&a).is_empty());
+    }
+}
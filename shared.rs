@@ -1,17 +1,53 @@
+pub mod batch;
+pub mod bench;
+pub mod budget;
+pub mod client;
+pub mod day_1;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+pub mod error;
+pub mod generate;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod metrics;
+pub mod num;
+pub mod oracle;
+pub mod output;
+pub mod parse;
+pub mod pool;
+pub mod range;
+pub mod verify;
+pub mod visualize;
+#[cfg(target_os = "linux")]
+pub mod watch;
+
+use budget::Budget;
+use error::Error;
+use generate::Rng;
+use metrics::Metrics;
+use output::Format;
+use parse::ParseError;
 use std::{
+    cell::Cell,
+    env,
+    fmt::{self, Display},
     fs::File,
-    io::{self, BufRead, Read},
-    path::Path,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, Instant},
 };
 
-pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
 pub fn read_string<P>(filename: P) -> io::Result<String>
 where
     P: AsRef<Path>,
@@ -21,3 +57,374 @@ where
     io::BufReader::new(file).read_to_string(&mut string)?;
     Ok(string)
 }
+
+/// Splits `input` into blocks separated by blank lines. Trailing newlines and
+/// CRLF line endings are tolerated so the same parsers work on files and on
+/// in-memory strings.
+pub fn split_blocks(input: &str) -> Vec<&str> {
+    let mut blocks = Vec::new();
+    let mut block_start: Option<usize> = None;
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        let blank = line.trim_end_matches(['\r', '\n']).is_empty();
+        match (blank, block_start) {
+            (true, Some(start)) => {
+                blocks.push(input[start..offset].trim_end_matches(['\r', '\n']));
+                block_start = None;
+            }
+            (false, None) => block_start = Some(offset),
+            _ => {}
+        }
+        offset += line.len();
+    }
+    if let Some(start) = block_start {
+        blocks.push(input[start..].trim_end_matches(['\r', '\n']));
+    }
+
+    blocks
+}
+
+/// Environment variable naming a directory that holds `{day}.txt` inputs.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where a day's puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    Path(PathBuf),
+}
+
+impl InputSource {
+    /// Resolves the input for `day`. An explicit argument wins (`-` meaning
+    /// stdin), followed by `{day}.txt` under `AOC_INPUT_DIR`, and finally
+    /// `./{day}.txt`.
+    pub fn resolve(day: u8, argument: Option<&str>) -> Self {
+        match argument {
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::Path(PathBuf::from(path)),
+            None => {
+                let directory = env::var_os(INPUT_DIR_VAR)
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| PathBuf::from("."));
+                InputSource::Path(directory.join(format!("{}.txt", day)))
+            }
+        }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            InputSource::Stdin => {
+                let mut string = String::new();
+                io::stdin().read_to_string(&mut string)?;
+                Ok(string)
+            }
+            InputSource::Path(path) => read_string(path),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::Path(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Whether an answer is known to be correct.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    /// The part ran out of budget and has no answer, or the answer rests on
+    /// a guess (see `mark_unknown`).
+    Unknown,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Solved => write!(f, "solved"),
+            Status::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Answer to one part of a day, with how long solving it took and what the
+/// solver recorded along the way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub part: u8,
+    /// The answer, or `None` if the part ran out of budget.
+    pub value: Option<String>,
+    pub status: Status,
+    pub elapsed: Duration,
+    pub metrics: Metrics,
+}
+
+/// Answers produced by a day's solution, in part order.
+pub type Answers = Vec<Answer>;
+
+thread_local! {
+    static UNKNOWN: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Flags the answer currently being computed on this thread as `Unknown`,
+/// e.g. when a search hits a limit and assumes the worst.
+pub fn mark_unknown() {
+    UNKNOWN.set(true);
+}
+
+/// A day's puzzle, split into parsing the raw input and solving each part.
+pub trait Solution {
+    /// Parsed puzzle input shared by both parts.
+    type Input;
+
+    /// Day of the puzzle, which also names its input file.
+    const DAY: u8;
+
+    /// Number of parts to solve (the final day only has one).
+    const PARTS: u8 = 2;
+
+    /// Parses the raw input, pointing any error at the offending text.
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    /// Random input that `parse` accepts, scaled by `size` (each day documents
+    /// what it controls). The same seed always produces the same input.
+    fn generate(rng: &mut Rng, size: usize) -> String;
+
+    fn part_1(input: &Self::Input) -> error::Result<impl Display>;
+
+    /// Days with one part keep this default, which fails with a usage error.
+    fn part_2(_input: &Self::Input) -> error::Result<impl Display> {
+        Err::<String, _>(Error::Usage(format!("day {} has no part 2", Self::DAY)))
+    }
+}
+
+/// Parts of `S` to solve given a requested part (or every part if `None`).
+pub fn selected_parts<S: Solution>(part: Option<u8>) -> impl Iterator<Item = u8> {
+    (1..=S::PARTS).filter(move |current| part.is_none_or(|part| part == *current))
+}
+
+/// Solves one part of already-parsed input within the configured budget
+/// (see `budget::configure`). Running out of budget gives an `Unknown`
+/// answer without a value rather than an error, and a part the day does not
+/// have is a usage error.
+pub fn solve_part<S: Solution>(input: &S::Input, part: u8) -> error::Result<Answer> {
+    let solver: fn(&S::Input) -> error::Result<String> = match part {
+        1 => |input| S::part_1(input).map(|answer| answer.to_string()),
+        2 if S::PARTS >= 2 => |input| S::part_2(input).map(|answer| answer.to_string()),
+        _ => return Err(Error::Usage(format!("day {} has no part {}", S::DAY, part))),
+    };
+
+    UNKNOWN.set(false);
+    metrics::take();
+    let armed = budget::start(&budget::configured());
+    let start = Instant::now();
+    let result = solver(input);
+    let elapsed = start.elapsed();
+    drop(armed);
+    let (value, status) = match result {
+        Ok(value) if UNKNOWN.replace(false) => (Some(value), Status::Unknown),
+        Ok(value) => (Some(value), Status::Solved),
+        Err(Error::BudgetExceeded(_)) => (None, Status::Unknown),
+        Err(error) => return Err(error),
+    };
+
+    Ok(Answer {
+        part,
+        value,
+        status,
+        elapsed,
+        metrics: metrics::take(),
+    })
+}
+
+/// Parses `input` and solves the requested part (or every part if `None`).
+pub fn solve<S: Solution>(input: &str, part: Option<u8>) -> error::Result<Answers> {
+    let input = S::parse(input)?;
+    answers::<S>(&input, part)
+}
+
+fn answers<S: Solution>(input: &S::Input, part: Option<u8>) -> error::Result<Answers> {
+    selected_parts::<S>(part)
+        .map(|current| solve_part::<S>(input, current))
+        .collect()
+}
+
+/// Reads the day's input from `source` and solves it.
+pub fn run<S: Solution>(source: &InputSource, part: Option<u8>) -> error::Result<Answers> {
+    let input = source.read().map_err(|error| Error::io(source, error))?;
+    let input = S::parse(&input).map_err(|error| error.in_file(source.to_string()))?;
+    answers::<S>(&input, part)
+}
+
+/// Entry point for the per-day binaries: prints each answer on its own line,
+/// or a JSON array with `--format json`. The optional positional argument
+/// selects the input (see `InputSource::resolve`), `--stats` adds the
+/// metrics each part recorded, and `--timeout` and `--max-steps` set the
+/// budget of each part. `--watch` keeps solving the input as it changes (see
+/// `watch::watch`), `--batch <dir|glob>` solves many inputs at once (see
+/// `batch::expand`), and `--arithmetic <checked|wide>` (`--wide` for short)
+/// picks the width answers are computed in (see `num::Mode`). Failures exit
+/// with the code of their `Error` variant, and parts that run out of budget
+/// print `unknown` and exit with `EXIT_BUDGET_EXCEEDED`.
+pub fn main<S: Solution>() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+    let options = match parse_main_args(&args) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            eprintln!(
+                "usage: {} [--format <text|json>] [--stats] [--timeout <seconds>] \
+                 [--max-steps <n|none>] [--arithmetic <checked|wide>] [--wide] [--watch] \
+                 [--batch <dir|glob>] [<path|->]",
+                S::DAY
+            );
+            return ExitCode::from(&error);
+        }
+    };
+
+    budget::configure(options.budget);
+    num::configure(options.mode);
+    if let Some(pattern) = options.batch {
+        let entries = match batch::expand(pattern) {
+            Ok(inputs) => batch::run_batch::<S>(&inputs, None, pool::default_jobs()),
+            Err(error) => {
+                eprintln!("error: {}", error);
+                return ExitCode::from(&error);
+            }
+        };
+        print!("{}", batch::report(S::DAY, &entries, options.format));
+        return ExitCode::from(batch::exit_code(&entries));
+    }
+    let source = InputSource::resolve(S::DAY, options.argument);
+    #[cfg(target_os = "linux")]
+    if options.watch {
+        let Err(error) = watch::watch::<S>(&source, None, options.stats);
+        eprintln!("error: {}", error);
+        return ExitCode::from(&error);
+    }
+    let result = run::<S>(&source, None);
+    match options.format {
+        Format::Text => {
+            if let Ok(answers) = &result {
+                for answer in answers {
+                    println!("{}", answer.value.as_deref().unwrap_or("unknown"));
+                }
+                if options.stats {
+                    for answer in answers {
+                        let heading = format!("part {}", answer.part);
+                        print!("{}", output::stats_text(&heading, &answer.metrics));
+                    }
+                }
+            }
+        }
+        Format::Json => {
+            let records = output::json_records(S::DAY, &source, &result, options.stats);
+            println!("{}", output::json_array(&records));
+        }
+    }
+
+    match result {
+        Ok(answers) => {
+            let exhausted = answers.iter().filter(|answer| answer.value.is_none());
+            let mut code = ExitCode::SUCCESS;
+            for answer in exhausted {
+                eprintln!("part {} ran out of budget", answer.part);
+                code = ExitCode::from(error::EXIT_BUDGET_EXCEEDED);
+            }
+            code
+        }
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::from(&error)
+        }
+    }
+}
+
+struct MainOptions<'a> {
+    format: Format,
+    stats: bool,
+    budget: Budget,
+    mode: num::Mode,
+    watch: bool,
+    batch: Option<&'a str>,
+    argument: Option<&'a str>,
+}
+
+fn parse_main_args(args: &[String]) -> error::Result<MainOptions<'_>> {
+    let mut options = MainOptions {
+        format: Format::Text,
+        stats: false,
+        budget: Budget {
+            steps: Some(budget::DEFAULT_STEPS),
+            ..Budget::default()
+        },
+        mode: num::Mode::Checked,
+        watch: false,
+        batch: None,
+        argument: None,
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| Error::Usage("missing value for --format".to_string()))?;
+                options.format = value.parse()?;
+            }
+            "--stats" => options.stats = true,
+            "--arithmetic" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| Error::Usage("missing value for --arithmetic".to_string()))?;
+                options.mode = value.parse()?;
+            }
+            "--wide" => options.mode = num::Mode::Wide,
+            #[cfg(target_os = "linux")]
+            "--watch" => options.watch = true,
+            #[cfg(not(target_os = "linux"))]
+            "--watch" => {
+                return Err(Error::Usage(
+                    "--watch is only supported on Linux".to_string(),
+                ));
+            }
+            "--batch" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| Error::Usage("missing value for --batch".to_string()))?;
+                options.batch = Some(value.as_str());
+            }
+            "--timeout" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| Error::Usage("missing value for --timeout".to_string()))?;
+                options.budget.time = Some(
+                    budget::parse_seconds(value)
+                        .ok_or_else(|| Error::Usage(format!("invalid timeout: {}", value)))?,
+                );
+            }
+            "--max-steps" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| Error::Usage("missing value for --max-steps".to_string()))?;
+                options.budget.steps = budget::parse_steps(value)
+                    .ok_or_else(|| Error::Usage(format!("invalid step limit: {}", value)))?;
+            }
+            _ if options.argument.is_none() => options.argument = Some(arg.as_str()),
+            _ => return Err(Error::Usage(format!("unexpected argument: {}", arg))),
+        }
+    }
+    if options.watch && options.format == Format::Json {
+        return Err(Error::Usage("--watch only prints text".to_string()));
+    }
+    if options.batch.is_some() && (options.watch || options.argument.is_some()) {
+        return Err(Error::Usage(
+            "--batch cannot be combined with --watch or an input".to_string(),
+        ));
+    }
+
+    Ok(options)
+}
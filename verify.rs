@@ -0,0 +1,228 @@
+use crate::{
+    Answers, error,
+    parse::{ParseContext, ParseError},
+};
+use std::path::PathBuf;
+
+/// Known-correct answer for one part of a day on a specific input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expectation {
+    pub day: u8,
+    pub part: u8,
+    pub input: PathBuf,
+    pub expected: String,
+}
+
+/// Result of checking one expectation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail { actual: String },
+    Error(String),
+}
+
+/// Parses an answers file: one `day part input expected` entry per line,
+/// separated by whitespace. Blank lines and lines starting with `#` are
+/// ignored. Days and parts must exist, where `parts[day - 1]` is the number
+/// of parts of each day, and each input may be listed only once per day and
+/// part.
+pub fn parse_answers(contents: &str, parts: &[u8]) -> Result<Vec<Expectation>, ParseError> {
+    let mut expectations = Vec::<Expectation>::new();
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let [day, part, input, expected]: [&str; 4] = line
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .try_into()
+            .map_err(|_| ParseError::at(contents, line, "expected 4 fields"))?;
+        let expectation = Expectation {
+            day: day.parse().context_at(contents, day, "invalid day")?,
+            part: part.parse().context_at(contents, part, "invalid part")?,
+            input: PathBuf::from(input),
+            expected: expected.to_string(),
+        };
+        let Some(day_parts) = (expectation.day as usize)
+            .checked_sub(1)
+            .and_then(|index| parts.get(index))
+        else {
+            let message = format!("invalid day: expected 1 to {}", parts.len());
+            return Err(ParseError::at(contents, day, message));
+        };
+        if !(1..=*day_parts).contains(&expectation.part) {
+            let message = format!(
+                "invalid part: day {} has no part {}",
+                expectation.day, expectation.part
+            );
+            return Err(ParseError::at(contents, part, message));
+        }
+        let same_entry = |other: &Expectation| {
+            (other.day, other.part, &other.input)
+                == (expectation.day, expectation.part, &expectation.input)
+        };
+        if expectations.iter().any(same_entry) {
+            let message = format!(
+                "duplicate answer for day {} part {} on {}",
+                expectation.day, expectation.part, input
+            );
+            return Err(ParseError::at(contents, line, message));
+        }
+        expectations.push(expectation);
+    }
+
+    Ok(expectations)
+}
+
+/// Compares the answers from a run against an expectation.
+pub fn check(expectation: &Expectation, answers: error::Result<Answers>) -> Outcome {
+    let answers = match answers {
+        Ok(answers) => answers,
+        Err(error) => return Outcome::Error(error.to_string()),
+    };
+
+    match answers
+        .into_iter()
+        .find(|answer| answer.part == expectation.part)
+    {
+        Some(answer) if answer.value.as_ref() == Some(&expectation.expected) => Outcome::Pass,
+        Some(answer) => Outcome::Fail {
+            actual: answer.value.unwrap_or_else(|| "unknown".to_string()),
+        },
+        None => Outcome::Error(format!("no answer for part {}", expectation.part)),
+    }
+}
+
+/// Renders a one-line report for an expectation and its outcome.
+pub fn report(expectation: &Expectation, outcome: &Outcome) -> String {
+    let label = format!(
+        "day {} part {} ({})",
+        expectation.day,
+        expectation.part,
+        expectation.input.display()
+    );
+    match outcome {
+        Outcome::Pass => format!("PASS  {}: {}", label, expectation.expected),
+        Outcome::Fail { actual } => format!(
+            "FAIL  {}: expected {}, got {}",
+            label, expectation.expected, actual
+        ),
+        Outcome::Error(error) => format!("ERROR {}: {}", label, error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Answer, Status, error::Error, metrics::Metrics};
+    use std::time::Duration;
+
+    /// Number of parts of each day.
+    const PARTS: [u8; 12] = [2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 1];
+
+    fn answer(part: u8, value: Option<&str>, status: Status) -> Answer {
+        Answer {
+            part,
+            value: value.map(str::to_string),
+            status,
+            elapsed: Duration::ZERO,
+            metrics: Metrics::default(),
+        }
+    }
+
+    #[test]
+    fn parses_answers() {
+        let contents = "# day part input expected\n\n1 1 1.txt 1066\n  12 1 big/12.txt 2\n";
+        let expectations = parse_answers(contents, &PARTS).unwrap();
+        assert_eq!(
+            expectations,
+            [
+                Expectation {
+                    day: 1,
+                    part: 1,
+                    input: PathBuf::from("1.txt"),
+                    expected: "1066".to_string(),
+                },
+                Expectation {
+                    day: 12,
+                    part: 1,
+                    input: PathBuf::from("big/12.txt"),
+                    expected: "2".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        let error = parse_answers("1 1 1.txt 1066\n1 2 1.txt\n", &PARTS).unwrap_err();
+        assert_eq!(
+            (error.line, error.message.as_str()),
+            (2, "expected 4 fields")
+        );
+
+        let error = parse_answers("1 x 1.txt 1066\n", &PARTS).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+        assert!(error.message.starts_with("invalid part"));
+
+        let error = parse_answers("1 1 1.txt 1066\n13 1 13.txt 5\n", &PARTS).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.message, "invalid day: expected 1 to 12");
+
+        let error = parse_answers("0 1 0.txt 5\n", &PARTS).unwrap_err();
+        assert_eq!(error.message, "invalid day: expected 1 to 12");
+
+        let error = parse_answers("3 3 3.txt 5\n", &PARTS).unwrap_err();
+        assert_eq!(error.message, "invalid part: day 3 has no part 3");
+
+        let error = parse_answers("12 1 12.txt 2\n12 2 12.txt 2\n", &PARTS).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(error.message, "invalid part: day 12 has no part 2");
+    }
+
+    #[test]
+    fn rejects_duplicates() {
+        let contents = "7 1 7.txt 21\n7 1 other/7.txt 40\n7 2 7.txt 40\n7 1 7.txt 22\n";
+        let error = parse_answers(contents, &PARTS).unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+        assert_eq!(error.message, "duplicate answer for day 7 part 1 on 7.txt");
+    }
+
+    #[test]
+    fn checks_and_reports_outcomes() {
+        let expectation = parse_answers("12 1 12.txt 2\n", &PARTS).unwrap().remove(0);
+        let outcome = |answers| {
+            let outcome = check(&expectation, answers);
+            let line = report(&expectation, &outcome);
+            (outcome, line)
+        };
+
+        assert_eq!(
+            outcome(Ok(vec![answer(1, Some("2"), Status::Solved)])),
+            (Outcome::Pass, "PASS  day 12 part 1 (12.txt): 2".to_string())
+        );
+        assert_eq!(
+            outcome(Ok(vec![answer(1, Some("3"), Status::Solved)])),
+            (
+                Outcome::Fail {
+                    actual: "3".to_string()
+                },
+                "FAIL  day 12 part 1 (12.txt): expected 2, got 3".to_string()
+            )
+        );
+        assert_eq!(
+            outcome(Ok(vec![answer(1, None, Status::Unknown)])).1,
+            "FAIL  day 12 part 1 (12.txt): expected 2, got unknown"
+        );
+        assert_eq!(
+            outcome(Ok(vec![answer(2, Some("2"), Status::Solved)])).1,
+            "ERROR day 12 part 1 (12.txt): no answer for part 1"
+        );
+        assert_eq!(
+            outcome(Err(Error::Infeasible("cycle".to_string()))).1,
+            "ERROR day 12 part 1 (12.txt): no solution: cycle"
+        );
+    }
+}
@@ -0,0 +1,302 @@
+use crate::{
+    Answers, InputSource, Solution,
+    error::{self, Error},
+    grid::Grid,
+    run,
+};
+use std::{
+    cell::RefCell,
+    fmt::Write as _,
+    fs,
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+/// Frames kept per run unless told otherwise. Searches such as day 12's can
+/// produce millions; past the limit only the latest is kept.
+pub const DEFAULT_FRAME_LIMIT: usize = 10_000;
+
+/// Frames per second `play` shows unless told otherwise.
+pub const DEFAULT_FPS: f64 = 10.0;
+
+/// One state of a grid-based solver.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    /// What happened in this step, e.g. `pass 2: removed 12 rolls`.
+    pub caption: String,
+    pub cells: Grid<char>,
+}
+
+/// Frames recorded by `record`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Recording {
+    pub frames: Vec<Frame>,
+    /// Frames left out between the last kept one and the latest.
+    pub dropped: usize,
+}
+
+struct Active {
+    recording: Recording,
+    limit: usize,
+}
+
+thread_local! {
+    static ACTIVE: RefCell<Option<Active>> = const { RefCell::new(None) };
+}
+
+/// Runs `work`, collecting the frames solvers record on this thread with
+/// `frame`. At most `limit` (at least one) frames are kept: the first
+/// `limit - 1` and the latest, so the final state is always shown.
+pub fn record<T>(limit: usize, work: impl FnOnce() -> T) -> (T, Recording) {
+    ACTIVE.set(Some(Active {
+        recording: Recording::default(),
+        limit: limit.max(1),
+    }));
+    let result = work();
+    let recording = ACTIVE
+        .take()
+        .map(|active| active.recording)
+        .unwrap_or_default();
+    (result, recording)
+}
+
+/// Whether `record` is collecting frames on this thread.
+pub fn is_recording() -> bool {
+    ACTIVE.with_borrow(Option::is_some)
+}
+
+/// Records a frame while `record` is running. Both closures only run then,
+/// so solvers can call this in hot loops.
+pub fn frame(caption: impl FnOnce() -> String, cells: impl FnOnce() -> Grid<char>) {
+    if !is_recording() {
+        return;
+    }
+
+    let frame = Frame {
+        caption: caption(),
+        cells: cells(),
+    };
+    ACTIVE.with_borrow_mut(|active| {
+        let Some(active) = active else {
+            return;
+        };
+        let frames = &mut active.recording.frames;
+        if frames.len() == active.limit {
+            frames.pop();
+            active.recording.dropped += 1;
+        }
+        frames.push(frame);
+    });
+}
+
+/// A day's answers and the frames recorded while solving it.
+pub type Recorded = (Answers, Recording);
+
+/// Reads and solves the day's input like `run`, recording its frames.
+/// Fails with a usage error if the day records none.
+pub fn run_recorded<S: Solution>(
+    source: &InputSource,
+    part: Option<u8>,
+    limit: usize,
+) -> error::Result<Recorded> {
+    let (result, recording) = record(limit, || run::<S>(source, part));
+    let answers = result?;
+    if recording.frames.is_empty() {
+        return Err(Error::Usage(format!(
+            "day {} has nothing to visualize",
+            S::DAY
+        )));
+    }
+
+    Ok((answers, recording))
+}
+
+/// ANSI style of a cell: paper and beams in their own colors, removed rolls
+/// in red and each day 12 piece (`A`, `B`, ...) on its own background.
+fn style(cell: char) -> &'static str {
+    const PIECES: [&str; 6] = ["30;41", "30;42", "30;43", "30;44", "30;45", "30;46"];
+    match cell {
+        '.' => "2",
+        '@' => "33",
+        'x' => "1;31",
+        '|' => "1;36",
+        '^' => "35",
+        'S' => "1;32",
+        'A'..='Z' => PIECES[(cell as u8 - b'A') as usize % PIECES.len()],
+        _ => "0",
+    }
+}
+
+/// The caption and grid of `frame`. With `color`, cells are styled with ANSI
+/// escapes and those that differ from `previous` are underlined.
+pub fn render(frame: &Frame, previous: Option<&Frame>, color: bool) -> String {
+    let mut text = format!("{}\n", frame.caption);
+    if !color {
+        text.push_str(&frame.cells.to_string());
+        return text;
+    }
+
+    let same_shape = |previous: &&Frame| {
+        (previous.cells.rows(), previous.cells.columns())
+            == (frame.cells.rows(), frame.cells.columns())
+    };
+    let previous = previous.filter(same_shape);
+    for row in 0..frame.cells.rows() {
+        for column in 0..frame.cells.columns() {
+            let cell = frame.cells[(row, column)];
+            let changed = previous.is_some_and(|previous| previous.cells[(row, column)] != cell);
+            let underline = if changed { ";4" } else { "" };
+            // Reset first so that bold or dim cells do not bleed into the next.
+            let _ = write!(text, "\x1b[0;{}{}m{}", style(cell), underline, cell);
+        }
+        text.push_str("\x1b[0m\n");
+    }
+
+    text
+}
+
+/// How `play` shows frames.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Playback {
+    /// Frames per second, or zero to wait for Enter before each frame.
+    pub fps: f64,
+    /// ANSI colors, and redrawing each frame in place instead of printing
+    /// them one after another.
+    pub color: bool,
+}
+
+impl Default for Playback {
+    fn default() -> Self {
+        Self {
+            fps: DEFAULT_FPS,
+            color: true,
+        }
+    }
+}
+
+/// Shows the frames on `out` one at a time, reading Enter presses from
+/// `keys` when stepping.
+pub fn play(
+    recording: &Recording,
+    playback: Playback,
+    out: &mut impl Write,
+    keys: &mut impl BufRead,
+) -> io::Result<()> {
+    let count = recording.frames.len();
+    let mut previous = None;
+    for (index, frame) in recording.frames.iter().enumerate() {
+        if playback.color {
+            // Clear the screen and move to its top left corner.
+            write!(out, "\x1b[2J\x1b[H")?;
+        }
+        write!(out, "[{}/{}] ", index + 1, count)?;
+        if index + 1 == count && recording.dropped > 0 {
+            write!(out, "({} frames skipped) ", recording.dropped)?;
+        }
+        write!(out, "{}", render(frame, previous, playback.color))?;
+        out.flush()?;
+        previous = Some(frame);
+
+        if index + 1 < count {
+            if playback.fps > 0.0 {
+                thread::sleep(Duration::from_secs_f64(1.0 / playback.fps));
+            } else {
+                keys.read_line(&mut String::new())?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes each frame, without colors, to `frame-00001.txt` and so on in
+/// `directory` (created if needed), returning the paths written.
+pub fn dump(recording: &Recording, directory: &Path) -> error::Result<Vec<PathBuf>> {
+    fs::create_dir_all(directory).map_err(|error| Error::io(directory.display(), error))?;
+    let mut paths = Vec::new();
+    for (index, frame) in recording.frames.iter().enumerate() {
+        let path = directory.join(format!("frame-{:05}.txt", index + 1));
+        fs::write(&path, render(frame, None, false))
+            .map_err(|error| Error::io(path.display(), error))?;
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(rows: &[&str]) -> Grid<char> {
+        Grid::parse(&rows.join("\n")).unwrap()
+    }
+
+    fn step(index: usize) {
+        frame(|| format!("step {}", index), || cells(&[".@", "@."]));
+    }
+
+    #[test]
+    fn records_only_when_asked() {
+        step(0);
+        assert!(!is_recording());
+
+        let (answer, recording) = record(3, || {
+            (1..=5).for_each(step);
+            42
+        });
+        assert_eq!(answer, 42);
+        let captions = recording
+            .frames
+            .iter()
+            .map(|frame| frame.caption.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(captions, ["step 1", "step 2", "step 5"]);
+        assert_eq!(recording.dropped, 2);
+        assert!(!is_recording());
+    }
+
+    #[test]
+    fn renders_changes() {
+        let before = Frame {
+            caption: "before".to_string(),
+            cells: cells(&["@.", "|S"]),
+        };
+        let after = Frame {
+            caption: "after".to_string(),
+            cells: cells(&["x.", "|S"]),
+        };
+        assert_eq!(render(&after, Some(&before), false), "after\nx.\n|S\n");
+
+        let colored = render(&after, Some(&before), true);
+        assert!(
+            colored.starts_with("after\n\x1b[0;1;31;4mx\x1b[0;2m."),
+            "{:?}",
+            colored
+        );
+        assert!(colored.contains("\x1b[0;1;36m|"));
+    }
+
+    #[test]
+    fn plays_and_dumps() {
+        let (_, recording) = record(10, || (1..=2).for_each(step));
+        let playback = Playback {
+            fps: 0.0,
+            color: false,
+        };
+        let mut out = Vec::new();
+        play(&recording, playback, &mut out, &mut "\n".as_bytes()).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out, "[1/2] step 1\n.@\n@.\n[2/2] step 2\n.@\n@.\n");
+
+        let directory = std::env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+        let paths = dump(&recording, &directory).unwrap();
+        assert_eq!(paths.len(), 2);
+        assert!(paths[1].ends_with("frame-00002.txt"));
+        assert_eq!(fs::read_to_string(&paths[0]).unwrap(), "step 1\n.@\n@.\n");
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,507 @@
+use crate::{
+    InputSource, Solution, Status,
+    error::{self, Error},
+    output, read_string, selected_parts, solve_part,
+};
+use std::{
+    convert::Infallible,
+    env,
+    ffi::{CString, OsStr, OsString, c_char, c_int, c_ulong},
+    fs::{self, File},
+    io::{self, Read},
+    os::{
+        fd::{AsRawFd, FromRawFd},
+        unix::{ffi::OsStrExt, process::CommandExt},
+    },
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus},
+    time::{Duration, Instant},
+};
+
+/// Environment variable carrying the last run's answers and timings across a
+/// rebuild, so the first run of the new binary still shows deltas.
+pub const PREVIOUS_VAR: &str = "AOC_WATCH_PREVIOUS";
+
+/// Environment variable naming the crate's directory. `cargo run` sets it;
+/// when it is unset the watcher leaves the sources alone and only watches the
+/// input, so no build machine path is baked into the binaries.
+pub const SOURCE_DIR_VAR: &str = "CARGO_MANIFEST_DIR";
+
+/// How long the watcher waits for a burst of writes to end before reporting
+/// it, so that one save gives one run.
+const SETTLE: Duration = Duration::from_millis(50);
+
+const IN_NONBLOCK: c_int = 0o4000;
+const IN_CLOEXEC: c_int = 0o2000000;
+const IN_CLOSE_WRITE: u32 = 0x8;
+const IN_MOVED_TO: u32 = 0x80;
+const IN_Q_OVERFLOW: u32 = 0x4000;
+const POLLIN: i16 = 0x1;
+
+/// Size of `struct inotify_event` before its name.
+const EVENT_HEADER: usize = 16;
+
+#[repr(C)]
+struct PollFd {
+    fd: c_int,
+    events: i16,
+    revents: i16,
+}
+
+unsafe extern "C" {
+    fn inotify_init1(flags: c_int) -> c_int;
+    fn inotify_add_watch(fd: c_int, pathname: *const c_char, mask: u32) -> c_int;
+    fn poll(fds: *mut PollFd, nfds: c_ulong, timeout: c_int) -> c_int;
+}
+
+/// Reports changes to a set of files through Linux inotify.
+pub struct Watcher {
+    inotify: File,
+    /// Watch descriptor of each file's directory, its name, and the path it
+    /// was added as.
+    watches: Vec<(c_int, OsString, PathBuf)>,
+}
+
+impl Watcher {
+    pub fn new() -> io::Result<Self> {
+        // SAFETY: takes no pointers; the result is checked before use.
+        let descriptor = unsafe { inotify_init1(IN_NONBLOCK | IN_CLOEXEC) };
+        if descriptor < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Self {
+            // SAFETY: `descriptor` is open and owned by nothing else.
+            inotify: unsafe { File::from_raw_fd(descriptor) },
+            watches: Vec::new(),
+        })
+    }
+
+    /// Watches `path`, which need not exist yet. Its directory is watched
+    /// rather than the file itself so that editors that save by renaming a
+    /// new copy over the file are still noticed.
+    pub fn add(&mut self, path: &Path) -> io::Result<()> {
+        let name = path
+            .file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not a path to a file"))?;
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let directory = CString::new(directory.as_os_str().as_bytes())?;
+        // SAFETY: `directory` is a NUL-terminated string that outlives the
+        // call.
+        let watch = unsafe {
+            inotify_add_watch(
+                self.inotify.as_raw_fd(),
+                directory.as_ptr(),
+                IN_CLOSE_WRITE | IN_MOVED_TO,
+            )
+        };
+        if watch < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        self.watches
+            .push((watch, name.to_os_string(), path.to_path_buf()));
+
+        Ok(())
+    }
+
+    /// Blocks until a watched file changes, then returns every watched path
+    /// that changed before things settled down.
+    pub fn wait(&mut self) -> io::Result<Vec<PathBuf>> {
+        let mut changed = Vec::new();
+        loop {
+            let timeout = match changed.is_empty() {
+                true => -1,
+                false => SETTLE.as_millis() as c_int,
+            };
+            if !self.poll(timeout)? {
+                return Ok(changed);
+            }
+            for path in self.read_changes()? {
+                if !changed.contains(&path) {
+                    changed.push(path);
+                }
+            }
+        }
+    }
+
+    /// Whether events arrived within `timeout` milliseconds (-1 waits
+    /// forever).
+    fn poll(&self, timeout: c_int) -> io::Result<bool> {
+        let mut request = PollFd {
+            fd: self.inotify.as_raw_fd(),
+            events: POLLIN,
+            revents: 0,
+        };
+        loop {
+            // SAFETY: `request` is a single valid `pollfd` for the call.
+            match unsafe { poll(&mut request, 1, timeout) } {
+                ready if ready >= 0 => return Ok(ready > 0),
+                _ => {
+                    let error = io::Error::last_os_error();
+                    if error.kind() != io::ErrorKind::Interrupted {
+                        return Err(error);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Drains the queued events into the watched paths they concern.
+    fn read_changes(&mut self) -> io::Result<Vec<PathBuf>> {
+        let mut changed = Vec::new();
+        let mut buffer = [0; 8192];
+        loop {
+            let length = match self.inotify.read(&mut buffer) {
+                Ok(length) => length,
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => return Ok(changed),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            };
+            for (watch, mask, name) in parse_events(&buffer[..length]) {
+                // Events were dropped, so any file may have changed.
+                let overflowed = mask & IN_Q_OVERFLOW != 0;
+                changed.extend(
+                    self.watches
+                        .iter()
+                        .filter(|(descriptor, file, _)| {
+                            overflowed || (*descriptor == watch && file.as_os_str() == name)
+                        })
+                        .map(|(_, _, path)| path.clone()),
+                );
+            }
+        }
+    }
+}
+
+/// Splits a buffer of `struct inotify_event`s into watch descriptor, mask
+/// and file name.
+fn parse_events(buffer: &[u8]) -> Vec<(c_int, u32, &OsStr)> {
+    let field = |offset: usize| buffer[offset..offset + 4].try_into().unwrap();
+    let mut events = Vec::new();
+    let mut offset = 0;
+    while offset + EVENT_HEADER <= buffer.len() {
+        let watch = c_int::from_ne_bytes(field(offset));
+        let mask = u32::from_ne_bytes(field(offset + 4));
+        let length = u32::from_ne_bytes(field(offset + 12)) as usize;
+        let name = &buffer[offset + EVENT_HEADER..offset + EVENT_HEADER + length];
+        let end = name
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(name.len());
+        events.push((watch, mask, OsStr::from_bytes(&name[..end])));
+        offset += EVENT_HEADER + length;
+    }
+
+    events
+}
+
+/// Answers and timings of one run, for comparison with the next.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Run {
+    parse: Option<Duration>,
+    parts: Vec<(u8, Option<String>, Duration)>,
+}
+
+impl Run {
+    fn part(&self, part: u8) -> Option<&(u8, Option<String>, Duration)> {
+        self.parts.iter().find(|(current, _, _)| *current == part)
+    }
+
+    /// One line for the parse time, then one per part of
+    /// `part nanoseconds [answer]`.
+    fn encode(&self) -> String {
+        let mut encoded = String::new();
+        if let Some(parse) = self.parse {
+            encoded.push_str(&format!("parse {}\n", parse.as_nanos()));
+        }
+        for (part, value, elapsed) in &self.parts {
+            encoded.push_str(&format!("{} {}", part, elapsed.as_nanos()));
+            if let Some(value) = value {
+                encoded.push_str(&format!(" {}", value));
+            }
+            encoded.push('\n');
+        }
+
+        encoded
+    }
+
+    /// Reverses `encode`, skipping lines it does not understand.
+    fn decode(encoded: &str) -> Self {
+        let mut run = Run::default();
+        for line in encoded.lines() {
+            let mut fields = line.splitn(3, ' ');
+            let (Some(key), Some(Ok(nanos))) = (fields.next(), fields.next().map(str::parse))
+            else {
+                continue;
+            };
+            let elapsed = Duration::from_nanos(nanos);
+            match key {
+                "parse" => run.parse = Some(elapsed),
+                _ => {
+                    if let Ok(part) = key.parse() {
+                        run.parts
+                            .push((part, fields.next().map(str::to_string), elapsed));
+                    }
+                }
+            }
+        }
+
+        run
+    }
+}
+
+/// Solves `source` with `S` and again every time it changes, printing the
+/// answers with each phase's time and its change since the previous run.
+///
+/// When run through `cargo run` (see `SOURCE_DIR_VAR`), the crate's sources
+/// are watched as well: when one changes, the running binary is rebuilt with
+/// cargo and replaced by the new build, which carries on watching. Only
+/// returns if watching itself fails.
+pub fn watch<S: Solution>(
+    source: &InputSource,
+    part: Option<u8>,
+    stats: bool,
+) -> error::Result<Infallible> {
+    let InputSource::Path(input) = source else {
+        return Err(Error::Usage("--watch needs an input file".to_string()));
+    };
+    // Captured up front: once rebuilt, the running binary's file is gone.
+    let binary = env::current_exe().map_err(|error| Error::io("current executable", error))?;
+
+    let mut watcher = Watcher::new().map_err(|error| Error::io("inotify", error))?;
+    watcher
+        .add(input)
+        .map_err(|error| Error::io(input.display(), error))?;
+    let source_dir = env::var_os(SOURCE_DIR_VAR).map(PathBuf::from);
+    let sources = source_dir.as_deref().map(source_files).unwrap_or_default();
+    for path in &sources {
+        watcher
+            .add(path)
+            .map_err(|error| Error::io(path.display(), error))?;
+    }
+    match &source_dir {
+        Some(directory) if sources.is_empty() => eprintln!(
+            "sources not found in {}; watching the input only",
+            directory.display()
+        ),
+        None => eprintln!("{} is not set; watching the input only", SOURCE_DIR_VAR),
+        Some(_) => {}
+    }
+
+    let mut previous = env::var(PREVIOUS_VAR)
+        .map(|encoded| Run::decode(&encoded))
+        .unwrap_or_default();
+    println!("watching {} (Ctrl-C to stop)", input.display());
+    let mut rerun = true;
+    loop {
+        if rerun {
+            previous = run_once::<S>(input, part, stats, &previous);
+        }
+
+        let changed = watcher
+            .wait()
+            .map_err(|error| Error::io("inotify", error))?;
+        for path in &changed {
+            println!("\n{} changed", path.display());
+        }
+        rerun = changed.contains(input);
+        if let Some(directory) = &source_dir
+            && changed.iter().any(|path| path != input)
+        {
+            let status = rebuild(&binary, directory, &previous)
+                .map_err(|error| Error::io(binary.display(), error))?;
+            eprintln!(
+                "error: build failed ({}); still running the old build",
+                status
+            );
+        }
+    }
+}
+
+/// The `.rs` files in the crate's `directory`, if it is still there.
+fn source_files(directory: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return Vec::new();
+    };
+
+    let mut sources = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension() == Some(OsStr::new("rs")))
+        .collect::<Vec<_>>();
+    sources.sort();
+
+    sources
+}
+
+/// Rebuilds `binary` from the crate in `directory` and replaces this process
+/// with it. Only returns if the build fails (with cargo's status) or the new
+/// binary cannot start.
+fn rebuild(binary: &Path, directory: &Path, previous: &Run) -> io::Result<ExitStatus> {
+    let name = binary
+        .file_stem()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "binary has no name"))?;
+    let mut cargo = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    cargo
+        .args(["build", "--quiet", "--bin"])
+        .arg(name)
+        .current_dir(directory);
+    if !cfg!(debug_assertions) {
+        cargo.arg("--release");
+    }
+    println!("rebuilding {}", name.to_string_lossy());
+    let status = cargo.status()?;
+    if !status.success() {
+        return Ok(status);
+    }
+
+    Err(Command::new(binary)
+        .args(env::args_os().skip(1))
+        .env(PREVIOUS_VAR, previous.encode())
+        .exec())
+}
+
+/// Parses and solves `input` once, printing what changed since `previous`.
+fn run_once<S: Solution>(input: &Path, part: Option<u8>, stats: bool, previous: &Run) -> Run {
+    let mut run = Run::default();
+    let text = match read_string(input) {
+        Ok(text) => text,
+        Err(error) => {
+            eprintln!("error: {}", Error::io(input.display(), error));
+            return run;
+        }
+    };
+
+    let start = Instant::now();
+    let parsed = S::parse(&text);
+    let elapsed = start.elapsed();
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            eprintln!("error: {}", error.in_file(input.display().to_string()));
+            return run;
+        }
+    };
+    println!("parse: {}", timing(elapsed, previous.parse));
+    run.parse = Some(elapsed);
+
+    for current in selected_parts::<S>(part) {
+        let answer = match solve_part::<S>(&parsed, current) {
+            Ok(answer) => answer,
+            Err(error) => {
+                eprintln!("part {}: error: {}", current, error);
+                continue;
+            }
+        };
+
+        let mut text = answer
+            .value
+            .clone()
+            .unwrap_or_else(|| "unknown (out of budget)".to_string());
+        if answer.value.is_some() && answer.status == Status::Unknown {
+            text.push_str(" (unknown)");
+        }
+        let before = previous.part(current);
+        if let Some((_, value, _)) = before
+            && *value != answer.value
+        {
+            let value = value.as_deref().unwrap_or("unknown");
+            text.push_str(&format!(" (was {})", value));
+        }
+        let before = before.map(|(_, _, elapsed)| *elapsed);
+        println!(
+            "part {}: {} in {}",
+            current,
+            text,
+            timing(answer.elapsed, before)
+        );
+        if stats {
+            let heading = format!("part {}", current);
+            print!("{}", output::stats_text(&heading, &answer.metrics));
+        }
+        run.parts.push((current, answer.value, answer.elapsed));
+    }
+
+    run
+}
+
+/// `elapsed`, followed by how much it grew or shrank since `previous`.
+fn timing(elapsed: Duration, previous: Option<Duration>) -> String {
+    match previous {
+        Some(previous) if elapsed >= previous => {
+            format!("{:.2?} (+{:.2?})", elapsed, elapsed - previous)
+        }
+        Some(previous) => format!("{:.2?} (-{:.2?})", elapsed, previous - elapsed),
+        None => format!("{:.2?}", elapsed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_writes_and_renames() {
+        let directory = env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let (input, other) = (directory.join("7.txt"), directory.join("8.txt"));
+        let mut watcher = Watcher::new().unwrap();
+        watcher.add(&input).unwrap();
+
+        fs::write(&other, "ignored").unwrap();
+        fs::write(&input, "first").unwrap();
+        assert_eq!(watcher.wait().unwrap(), [input.as_path()]);
+
+        // Editors often save by renaming a fresh copy over the file.
+        fs::write(&other, "second").unwrap();
+        fs::rename(&other, &input).unwrap();
+        assert_eq!(watcher.wait().unwrap(), [input.as_path()]);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn parses_event_buffers() {
+        let mut buffer = Vec::new();
+        for (watch, mask, name) in [
+            (1, IN_CLOSE_WRITE, &b"7.txt\0\0\0"[..]),
+            (2, IN_MOVED_TO, b""),
+        ] {
+            buffer.extend((watch as c_int).to_ne_bytes());
+            buffer.extend(mask.to_ne_bytes());
+            buffer.extend(0u32.to_ne_bytes());
+            buffer.extend((name.len() as u32).to_ne_bytes());
+            buffer.extend(name);
+        }
+        assert_eq!(
+            parse_events(&buffer),
+            [
+                (1, IN_CLOSE_WRITE, OsStr::new("7.txt")),
+                (2, IN_MOVED_TO, OsStr::new(""))
+            ]
+        );
+    }
+
+    #[test]
+    fn previous_run_survives_encoding() {
+        let run = Run {
+            parse: Some(Duration::from_micros(15)),
+            parts: vec![
+                (1, Some("42 and more".to_string()), Duration::from_millis(3)),
+                (2, None, Duration::from_secs(1)),
+            ],
+        };
+        assert_eq!(Run::decode(&run.encode()), run);
+        assert_eq!(Run::decode("garbage\n"), Run::default());
+    }
+
+    #[test]
+    fn timing_shows_change() {
+        let ms = Duration::from_millis;
+        assert_eq!(timing(ms(3), None), "3.00ms");
+        assert_eq!(timing(ms(3), Some(ms(1))), "3.00ms (+2.00ms)");
+        assert_eq!(timing(ms(1), Some(ms(3))), "1.00ms (-2.00ms)");
+    }
+}